@@ -0,0 +1,183 @@
+use tauri::{Manager, AppHandle, Emitter};
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader};
+use std::process::Stdio;
+use std::thread;
+use tokio::io::AsyncWriteExt;
+use crate::{models::archive::ArchiveProgress, utils::{git_command, git_command_async}};
+
+/// Exporta um commit, branch ou tag como `.zip` ou `.tar.gz`.
+/// O progresso é emitido no evento `archive-progress`; atributos `export-ignore` são respeitados pelo próprio `git archive`
+/// e descontados do total de arquivos.
+#[tauri::command]
+pub async fn export_archive(
+    app: AppHandle,
+    repo_path: String,
+    revision: String,
+    format: String,
+    output_path: String,
+    subdirectory: Option<String>,
+    prefix: Option<String>,
+) -> Result<String, String> {
+    let format = match format.as_str() {
+        "zip" => "zip",
+        "tar.gz" | "tgz" => "tar.gz",
+        _ => return Err("Formato inválido. Use zip ou tar.gz.".into()),
+    };
+
+    let window = app.get_webview_window("main")
+        .ok_or_else(|| "Janela principal não encontrada".to_string())?;
+
+    let subdirectory = subdirectory
+        .map(|s| s.trim().trim_matches('/').to_string())
+        .filter(|s| !s.is_empty() && s != ".");
+
+    // Conta os arquivos que o archive vai incluir para calcular o percentual
+    let mut ls_tree = git_command_async(&repo_path);
+    ls_tree.args(["ls-tree", "-r", "-t", "-z", &revision]);
+    if let Some(dir) = &subdirectory {
+        ls_tree.arg("--").arg(dir);
+    }
+
+    let ls_output = ls_tree.output().await.map_err(|e| e.to_string())?;
+    if !ls_output.status.success() {
+        return Err(String::from_utf8_lossy(&ls_output.stderr).to_string());
+    }
+
+    // "<modo> <tipo> <hash>\t<caminho>"; diretórios vão com "/" para casar padrões como "docs/ export-ignore"
+    let mut files = Vec::new();
+    let mut attr_paths = Vec::new();
+    for entry in ls_output.stdout.split(|&b| b == 0).filter(|e| !e.is_empty()) {
+        let entry = String::from_utf8_lossy(entry);
+        let Some((meta, path)) = entry.split_once('\t') else { continue };
+        match meta.split(' ').nth(1) {
+            Some("tree") => attr_paths.push(format!("{}/", path)),
+            Some("blob") => {
+                attr_paths.push(path.to_string());
+                files.push(path.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    let ignored = export_ignored(&repo_path, &revision, &attr_paths).await;
+    let prefix = prefix.map(|p| p.trim().trim_matches('/').to_string()).filter(|p| !p.is_empty());
+    let expected: HashSet<String> = files
+        .into_iter()
+        .filter(|file| !is_export_ignored(file, &ignored))
+        .map(|file| match &prefix {
+            Some(p) => format!("{}/{}", p, file),
+            None => file,
+        })
+        .collect();
+    let total_files = expected.len();
+
+    let mut cmd = git_command(&repo_path);
+    cmd.arg("archive")
+        .arg(format!("--format={}", format))
+        .arg("--verbose")
+        .arg(format!("--output={}", output_path));
+
+    if let Some(p) = &prefix {
+        cmd.arg(format!("--prefix={}/", p));
+    }
+
+    cmd.arg(&revision);
+    if let Some(dir) = &subdirectory {
+        cmd.arg("--").arg(dir);
+    }
+
+    cmd.stdout(Stdio::null()).stderr(Stdio::piped());
+    let mut child = cmd.spawn().map_err(|e| e.to_string())?;
+    let stderr = child.stderr.take().ok_or("Falha ao capturar a saída do git archive")?;
+
+    let progress = move |status: &str, files_done: usize, current_file: Option<String>, error: Option<String>| {
+        let _ = window.emit("archive-progress", ArchiveProgress {
+            status: status.into(),
+            files_done,
+            total_files,
+            current_file,
+            output_path: output_path.clone(),
+            error,
+        });
+    };
+
+    thread::spawn(move || {
+        // Com --verbose o git escreve no stderr cada arquivo adicionado
+        let mut files_done = 0;
+        let mut last_lines: Vec<String> = Vec::new();
+
+        // Avisos e erros também saem no stderr: só contam as linhas que são arquivos esperados
+        for line in BufReader::new(stderr).split(b'\n').map_while(Result::ok) {
+            let line = String::from_utf8_lossy(&line).trim_end_matches('\r').to_string();
+            if expected.contains(&line) {
+                files_done += 1;
+                progress("running", files_done.min(total_files), Some(line), None);
+            } else if !line.ends_with('/') {
+                last_lines.push(line);
+                if last_lines.len() > 5 {
+                    last_lines.remove(0);
+                }
+            }
+        }
+
+        match child.wait() {
+            Ok(status) if status.success() => progress("finished", total_files, None, None),
+            Ok(_) => progress("error", files_done, None, Some(last_lines.join("\n"))),
+            Err(e) => progress("error", files_done, None, Some(e.to_string())),
+        }
+    });
+
+    Ok("Exportação iniciada".into())
+}
+
+/// Caminhos (arquivos e diretórios terminados em "/") marcados com `export-ignore`.
+/// Como o `git archive`, lê os atributos da própria revisão quando o Git suporta `--source` (2.40+).
+async fn export_ignored(repo_path: &str, revision: &str, paths: &[String]) -> HashSet<String> {
+    if paths.is_empty() {
+        return HashSet::new();
+    }
+    let input: Vec<u8> = paths.iter().flat_map(|p| p.bytes().chain([0])).collect();
+
+    let source = format!("--source={}", revision);
+    let mut output = check_attr_stdin(repo_path, &[&source], &input).await;
+    if output.is_none() {
+        output = check_attr_stdin(repo_path, &[], &input).await;
+    }
+
+    // Saída com -z: "<caminho>\0<atributo>\0<valor>\0"
+    let output = output.unwrap_or_default();
+    let fields: Vec<&[u8]> = output.split(|&b| b == 0).collect();
+    fields
+        .chunks_exact(3)
+        .filter(|f| f[2] == b"set")
+        .map(|f| String::from_utf8_lossy(f[0]).to_string())
+        .collect()
+}
+
+async fn check_attr_stdin(repo_path: &str, extra: &[&str], input: &[u8]) -> Option<Vec<u8>> {
+    let mut child = git_command_async(repo_path)
+        .arg("check-attr")
+        .args(extra)
+        .args(["--stdin", "-z", "export-ignore"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    // Escreve e lê ao mesmo tempo para o pipe não travar em árvores grandes
+    let mut stdin = child.stdin.take()?;
+    let write = async move {
+        let _ = stdin.write_all(input).await;
+    };
+    let (_, output) = tokio::join!(write, child.wait_with_output());
+
+    output.ok().filter(|o| o.status.success()).map(|o| o.stdout)
+}
+
+/// Um arquivo fica de fora se ele ou qualquer diretório acima dele tiver `export-ignore`
+fn is_export_ignored(file: &str, ignored: &HashSet<String>) -> bool {
+    ignored.contains(file)
+        || file.match_indices('/').any(|(i, _)| ignored.contains(&file[..=i]))
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::Stdio;
+use crate::{
+    models::author::{AuthorRewriteOptions, AuthorRewriteResult},
+    utils::{git_command, run_git},
+};
+
+/// Corrige nome e/ou e-mail do autor em commits ainda não enviados da branch atual.
+/// Os commits são recriados com `git commit-tree` (mesma árvore, mensagem e datas), então o
+/// working tree e o índice não são tocados. Assinaturas GPG/SSH dos commits reescritos se perdem.
+#[tauri::command]
+pub fn rewrite_commit_author(repo_path: String, options: AuthorRewriteOptions) -> Result<AuthorRewriteResult, String> {
+    let name = options.name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    let email = options.email.map(|e| e.trim().to_string()).filter(|e| !e.is_empty());
+    if name.is_none() && email.is_none() {
+        return Err("Informe o novo nome ou e-mail do autor".into());
+    }
+    let old_email = options.old_email.map(|e| e.trim().to_lowercase()).filter(|e| !e.is_empty());
+
+    let branch = run_git(&repo_path, &["symbolic-ref", "--short", "-q", "HEAD"])
+        .map(|b| b.trim().to_string())
+        .map_err(|_| "HEAD destacado: faça checkout de uma branch antes de corrigir o autor".to_string())?;
+    let old_head = run_git(&repo_path, &["rev-parse", "HEAD"])?.trim().to_string();
+
+    let mut range = vec!["rev-list", "--reverse", "--topo-order", "HEAD"];
+    let base = options.base.filter(|b| !b.trim().is_empty());
+    let exclude;
+    match &base {
+        Some(base) => {
+            exclude = format!("^{}", base);
+            range.push(&exclude);
+        }
+        None => range.extend(["--not", "--remotes"]),
+    }
+    let commits: Vec<String> = run_git(&repo_path, &range)?.lines().map(String::from).collect();
+    if commits.is_empty() {
+        return Err("Nenhum commit local para corrigir".into());
+    }
+
+    if base.is_some() {
+        let mut unpushed = range.clone();
+        unpushed.extend(["--not", "--remotes"]);
+        let unpushed = run_git(&repo_path, &unpushed)?.lines().count();
+        if unpushed < commits.len() {
+            return Err(format!(
+                "{} commit(s) do intervalo já estão em um remoto. Reescrevê-los exigiria force push.",
+                commits.len() - unpushed
+            ));
+        }
+    }
+
+    // Commits antigos -> novos, para religar os pais
+    let mut mapping: HashMap<String, String> = HashMap::new();
+    let mut rewritten = 0;
+
+    for sha in &commits {
+        // Em bytes: a mensagem pode estar em outro encoding (cabeçalho "encoding") e volta intacta
+        let output = git_command(&repo_path)
+            .args(["cat-file", "commit", sha])
+            .output()
+            .map_err(|e| e.to_string())?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).to_string());
+        }
+        let raw = output.stdout;
+        let (header, message) = match raw.windows(2).position(|w| w == b"\n\n") {
+            Some(end) => (&raw[..end], &raw[end + 2..]),
+            None => (&raw[..], &[][..]),
+        };
+
+        let mut tree = String::new();
+        let mut parents = Vec::new();
+        let mut author = None;
+        let mut committer = None;
+        let mut encoding = None;
+        for line in header.split(|&b| b == b'\n') {
+            let (key, value) = match line.iter().position(|&b| b == b' ') {
+                Some(space) => (&line[..space], &line[space + 1..]),
+                None => continue,
+            };
+            // Os nomes vão para o commit-tree por variáveis de ambiente, que precisam ser texto
+            let text = || {
+                std::str::from_utf8(value)
+                    .map_err(|_| format!("O commit {} tem autor ou committer fora do UTF-8 e não pode ser reescrito", sha))
+            };
+            match key {
+                b"tree" => tree = text()?.to_string(),
+                b"parent" => {
+                    let value = text()?;
+                    parents.push(mapping.get(value).cloned().unwrap_or_else(|| value.to_string()));
+                }
+                b"author" => author = parse_ident(text()?),
+                b"committer" => committer = parse_ident(text()?),
+                b"encoding" => encoding = Some(String::from_utf8_lossy(value).to_string()),
+                _ => {}
+            }
+        }
+        let (mut author, mut committer) = match (author, committer) {
+            (Some(a), Some(c)) => (a, c),
+            _ => return Err(format!("Não foi possível ler o commit {}", sha)),
+        };
+
+        let matches = |ident: &Ident| old_email.as_ref().is_none_or(|old| ident.email.to_lowercase() == *old);
+        if matches(&author) {
+            rewritten += 1;
+            // O committer também recebe a correção quando tinha a mesma identidade errada
+            if matches(&committer) {
+                committer.fix(&name, &email);
+            }
+            author.fix(&name, &email);
+        }
+
+        let mut cmd = git_command(&repo_path);
+        // Sem isso o commit-tree gravaria a mensagem original marcada como UTF-8
+        if let Some(encoding) = &encoding {
+            cmd.arg("-c").arg(format!("i18n.commitEncoding={}", encoding));
+        }
+        cmd.args(["commit-tree", &tree]);
+        for parent in &parents {
+            cmd.args(["-p", parent]);
+        }
+        let mut child = cmd
+            .env("GIT_AUTHOR_NAME", &author.name)
+            .env("GIT_AUTHOR_EMAIL", &author.email)
+            .env("GIT_AUTHOR_DATE", &author.date)
+            .env("GIT_COMMITTER_NAME", &committer.name)
+            .env("GIT_COMMITTER_EMAIL", &committer.email)
+            .env("GIT_COMMITTER_DATE", &committer.date)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| e.to_string())?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(message).map_err(|e| e.to_string())?;
+        }
+        let output = child.wait_with_output().map_err(|e| e.to_string())?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).to_string());
+        }
+        mapping.insert(sha.clone(), String::from_utf8_lossy(&output.stdout).trim().to_string());
+    }
+
+    let new_head = mapping.get(&old_head).cloned().unwrap_or_else(|| old_head.clone());
+    let branch_ref = format!("refs/heads/{}", branch);
+    // Só atualiza se a branch não mudou enquanto os commits eram recriados
+    run_git(&repo_path, &["update-ref", "-m", "rewrite author", &branch_ref, &new_head, &old_head])?;
+
+    Ok(AuthorRewriteResult {
+        branch,
+        old_head,
+        new_head,
+        total: commits.len(),
+        rewritten,
+    })
+}
+
+struct Ident {
+    name: String,
+    email: String,
+    date: String, // Formato interno do git: "<timestamp> <fuso>"
+}
+
+impl Ident {
+    fn fix(&mut self, name: &Option<String>, email: &Option<String>) {
+        if let Some(name) = name {
+            self.name = name.clone();
+        }
+        if let Some(email) = email {
+            self.email = email.clone();
+        }
+    }
+}
+
+/// "Nome <email> 1700000000 -0300"
+fn parse_ident(value: &str) -> Option<Ident> {
+    let (name, rest) = value.split_once(" <")?;
+    let (email, date) = rest.split_once("> ")?;
+    Some(Ident {
+        name: name.to_string(),
+        email: email.to_string(),
+        date: date.to_string(),
+    })
+}
@@ -0,0 +1,179 @@
+use tauri::{Manager, AppHandle, Emitter};
+use std::io::{BufRead, BufReader, Read};
+use std::process::Stdio;
+use std::thread;
+use crate::{
+    commands::sequencer::git_path,
+    models::bisect::{BisectCandidate, BisectOutputEvent, BisectRunFinished, BisectStatus, BisectVerdict},
+    tests::project_type::detect_project_type,
+    utils::{git_command, run_git},
+};
+
+const MAX_CANDIDATES: usize = 200;
+
+/// Inicia o bisect com o commit ruim e um ou mais commits bons
+#[tauri::command]
+pub fn bisect_start(repo_path: String, bad: String, good: Vec<String>) -> Result<BisectStatus, String> {
+    if good.is_empty() {
+        return Err("Informe pelo menos um commit bom".into());
+    }
+
+    let mut args = vec!["bisect", "start", bad.as_str()];
+    args.extend(good.iter().map(|g| g.as_str()));
+    let message = run_git(&repo_path, &args)?;
+    read_status(&repo_path, message)
+}
+
+/// Marca o commit atual como bom, ruim ou pulado
+#[tauri::command]
+pub fn bisect_mark(repo_path: String, verdict: BisectVerdict) -> Result<BisectStatus, String> {
+    let message = run_git(&repo_path, &["bisect", verdict.as_str()])?;
+    read_status(&repo_path, message)
+}
+
+#[tauri::command]
+pub fn get_bisect_status(repo_path: String) -> Result<BisectStatus, String> {
+    read_status(&repo_path, String::new())
+}
+
+#[tauri::command]
+pub fn bisect_reset(repo_path: String) -> Result<String, String> {
+    run_git(&repo_path, &["bisect", "reset"])
+}
+
+/// Roda `git bisect run` com o comando informado ou, sem comando, com o de testes detectado no projeto.
+/// A saída chega pelo evento "bisect-output" e o resultado por "bisect-finished".
+#[tauri::command]
+pub async fn bisect_run(app: AppHandle, repo_path: String, command: Option<String>) -> Result<String, String> {
+    let window = app.get_webview_window("main")
+        .ok_or_else(|| "Janela principal não encontrada".to_string())?;
+
+    if !git_path(&repo_path, "BISECT_START")?.exists() {
+        return Err("Nenhum bisect em andamento".into());
+    }
+
+    let command = match command.filter(|c| !c.trim().is_empty()) {
+        Some(command) => command,
+        None => detect_project_type(repo_path.clone())
+            .await?
+            .test_command()
+            .map(String::from)
+            .ok_or("Nenhum comando de testes detectado; informe o comando")?,
+    };
+
+    let (shell, arg) = if cfg!(target_os = "windows") {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+
+    let mut child = git_command(&repo_path)
+        .args(["bisect", "run", shell, arg, &command])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    let stdout = child.stdout.take().ok_or("Falha ao capturar a saída")?;
+    let stderr = child.stderr.take().ok_or("Falha ao capturar a saída")?;
+
+    thread::spawn(move || {
+        let stderr_window = window.clone();
+        let stderr_reader = thread::spawn(move || stream_lines(&stderr_window, "stderr", stderr));
+        stream_lines(&window, "stdout", stdout);
+        let _ = stderr_reader.join();
+
+        let finished = match child.wait() {
+            Ok(_) => match read_status(&repo_path, String::new()) {
+                Ok(status) => BisectRunFinished { command, status: Some(status), error: None },
+                Err(e) => BisectRunFinished { command, status: None, error: Some(e) },
+            },
+            Err(e) => BisectRunFinished { command, status: None, error: Some(e.to_string()) },
+        };
+        let _ = window.emit("bisect-finished", finished);
+    });
+
+    Ok("Bisect automático iniciado".into())
+}
+
+fn stream_lines<R: Read>(window: &tauri::WebviewWindow, stream: &str, reader: R) {
+    for line in BufReader::new(reader).lines().map_while(Result::ok) {
+        let _ = window.emit("bisect-output", BisectOutputEvent { stream: stream.to_string(), line });
+    }
+}
+
+fn read_status(repo_path: &str, message: String) -> Result<BisectStatus, String> {
+    if !git_path(repo_path, "BISECT_START")?.exists() {
+        return Ok(BisectStatus {
+            in_progress: false,
+            current: None,
+            bad: None,
+            good: Vec::new(),
+            skipped: Vec::new(),
+            remaining: 0,
+            estimated_steps: 0,
+            first_bad: None,
+            candidates: Vec::new(),
+            message,
+        });
+    }
+
+    let bisect_refs = |pattern: &str| -> Vec<String> {
+        run_git(repo_path, &["for-each-ref", "--format=%(objectname)", pattern])
+            .map(|out| out.lines().map(String::from).collect())
+            .unwrap_or_default()
+    };
+    let good = bisect_refs("refs/bisect/good-*");
+    let skipped = bisect_refs("refs/bisect/skip-*");
+    let bad = bisect_refs("refs/bisect/bad").into_iter().next();
+    let current = run_git(repo_path, &["rev-parse", "HEAD"]).ok().map(|s| s.trim().to_string());
+
+    // Sem commit ruim ainda não há faixa para calcular
+    let (remaining, estimated_steps, first_bad, candidates) = match &bad {
+        Some(bad) => {
+            let mut range = vec![bad.as_str(), "--not"];
+            range.extend(good.iter().map(|g| g.as_str()));
+
+            let mut vars_args = vec!["rev-list", "--bisect-vars"];
+            vars_args.extend(range.iter());
+            let vars = run_git(repo_path, &vars_args)?;
+            let var = |name: &str| -> String {
+                vars.lines()
+                    .find_map(|l| l.strip_prefix(name).and_then(|v| v.strip_prefix('=')))
+                    .unwrap_or("")
+                    .trim_matches('\'')
+                    .to_string()
+            };
+
+            let remaining: usize = var("bisect_all").parse().unwrap_or(0);
+            let steps = var("bisect_steps").parse().unwrap_or(0);
+            // Quando só resta o próprio commit ruim, ele é o primeiro ruim
+            let first_bad = if remaining == 1 { Some(bad.clone()) } else { None };
+
+            let limit = format!("--max-count={}", MAX_CANDIDATES);
+            let mut log_args = vec!["log", "--format=%H%x1f%s", limit.as_str()];
+            log_args.extend(range.iter());
+            let candidates = run_git(repo_path, &log_args)?
+                .lines()
+                .filter_map(|l| l.split_once('\x1f'))
+                .map(|(hash, subject)| BisectCandidate { hash: hash.to_string(), subject: subject.to_string() })
+                .collect();
+
+            (remaining, steps, first_bad, candidates)
+        }
+        None => (0, 0, None, Vec::new()),
+    };
+
+    Ok(BisectStatus {
+        in_progress: true,
+        current,
+        bad,
+        good,
+        skipped,
+        remaining,
+        estimated_steps,
+        first_bad,
+        candidates,
+        message,
+    })
+}
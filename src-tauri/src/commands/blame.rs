@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::path::Path;
+use crate::{models::blame::{BlameHunk, BlameLine, BlameOptions, BlameResult}, utils::git_command_async};
+
+const IGNORE_REVS_FILE: &str = ".git-blame-ignore-revs";
+
+#[derive(Default, Clone)]
+struct BlameCommitInfo {
+    author: String,
+    email: String,
+    date: i64,
+    timezone: String,
+    summary: String,
+    file_path: String,
+    previous: Option<(String, String)>,
+    boundary: bool,
+}
+
+#[tauri::command]
+pub async fn get_blame(
+    repo_path: String,
+    revision: String,
+    file_path: String,
+    options: Option<BlameOptions>,
+) -> Result<BlameResult, String> {
+    let options = options.unwrap_or_default();
+    let hunks = run_blame(&repo_path, &revision, &file_path, None, &options).await?;
+
+    Ok(BlameResult {
+        revision,
+        file_path,
+        hunks,
+        focus_line: None,
+    })
+}
+
+/// Blame do arquivo no pai do commit que introduziu a linha escolhida.
+/// `commit_hash`, `file_path` e `line_number` vêm do hunk atual (linha original).
+#[tauri::command]
+pub async fn get_blame_prior(
+    repo_path: String,
+    commit_hash: String,
+    file_path: String,
+    line_number: usize,
+    options: Option<BlameOptions>,
+) -> Result<BlameResult, String> {
+    let options = options.unwrap_or_default();
+
+    let origin = run_blame(&repo_path, &commit_hash, &file_path, Some(line_number), &options).await?;
+    let (previous_hash, previous_path) = origin
+        .first()
+        .and_then(|hunk| hunk.previous_hash.clone().zip(hunk.previous_path.clone()))
+        .ok_or("Esta linha não possui histórico anterior (o arquivo foi criado neste commit)")?;
+
+    let hunks = run_blame(&repo_path, &previous_hash, &previous_path, None, &options).await?;
+    let total_lines: usize = hunks.iter().map(|h| h.lines.len()).sum();
+
+    Ok(BlameResult {
+        revision: previous_hash,
+        file_path: previous_path,
+        hunks,
+        focus_line: Some(line_number.clamp(1, total_lines.max(1))),
+    })
+}
+
+async fn run_blame(
+    repo_path: &str,
+    revision: &str,
+    file_path: &str,
+    line: Option<usize>,
+    options: &BlameOptions,
+) -> Result<Vec<BlameHunk>, String> {
+    let mut cmd = git_command_async(repo_path);
+    cmd.args(["blame", "--porcelain"]);
+
+    if options.ignore_whitespace {
+        cmd.arg("-w");
+    }
+    if options.detect_moves {
+        cmd.arg("-M");
+    }
+    if options.detect_copies {
+        cmd.arg("-C");
+    }
+
+    match options.ignore_revs {
+        Some(true) if Path::new(repo_path).join(IGNORE_REVS_FILE).exists() => {
+            cmd.args(["--ignore-revs-file", IGNORE_REVS_FILE]);
+        }
+        // Nome vazio limpa a lista, inclusive a vinda de blame.ignoreRevsFile
+        Some(false) => {
+            cmd.args(["--ignore-revs-file", ""]);
+        }
+        _ => {}
+    }
+
+    if let Some(n) = line {
+        cmd.arg("-L").arg(format!("{},{}", n, n));
+    }
+
+    if !revision.trim().is_empty() {
+        cmd.arg(revision);
+    }
+
+    let output = cmd
+        .arg("--")
+        .arg(file_path)
+        .output()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    Ok(parse_porcelain(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_porcelain(raw: &str) -> Vec<BlameHunk> {
+    let mut commits: HashMap<String, BlameCommitInfo> = HashMap::new();
+    let mut hunks: Vec<BlameHunk> = Vec::new();
+    let mut lines = raw.lines();
+
+    while let Some(header) = lines.next() {
+        // "<sha> <linha original> <linha final> [<linhas no grupo>]"
+        let parts: Vec<&str> = header.split(' ').collect();
+        if parts.len() < 3 {
+            continue;
+        }
+
+        let hash = parts[0].to_string();
+        let original_line_number = parts[1].parse::<usize>().unwrap_or(0);
+        let line_number = parts[2].parse::<usize>().unwrap_or(0);
+        let starts_group = parts.len() >= 4;
+
+        // O Git só repete os metadados na primeira vez em que o commit aparece
+        let info = commits.entry(hash.clone()).or_default();
+        let mut content = String::new();
+
+        for detail in lines.by_ref() {
+            if let Some(text) = detail.strip_prefix('\t') {
+                content = text.to_string();
+                break;
+            }
+
+            let (key, value) = detail.split_once(' ').unwrap_or((detail, ""));
+            match key {
+                "author" => info.author = value.to_string(),
+                "author-mail" => info.email = value.trim_matches(|c| c == '<' || c == '>').to_string(),
+                "author-time" => info.date = value.parse::<i64>().unwrap_or(0),
+                "author-tz" => info.timezone = value.to_string(),
+                "summary" => info.summary = value.to_string(),
+                "boundary" => info.boundary = true,
+                "filename" => info.file_path = value.to_string(),
+                "previous" => {
+                    info.previous = value
+                        .split_once(' ')
+                        .map(|(sha, path)| (sha.to_string(), path.to_string()));
+                }
+                _ => {}
+            }
+        }
+
+        let needs_new_hunk = starts_group || hunks.last().is_none_or(|h| h.hash != hash);
+        if needs_new_hunk {
+            let info = info.clone();
+            let (previous_hash, previous_path) = info.previous.unzip();
+            hunks.push(BlameHunk {
+                hash,
+                author: info.author,
+                email: info.email,
+                date: info.date,
+                timezone: info.timezone,
+                summary: info.summary,
+                file_path: info.file_path,
+                previous_hash,
+                previous_path,
+                boundary: info.boundary,
+                lines: Vec::new(),
+            });
+        }
+
+        if let Some(hunk) = hunks.last_mut() {
+            hunk.lines.push(BlameLine {
+                line_number,
+                original_line_number,
+                content,
+            });
+        }
+    }
+
+    hunks
+}
@@ -1,6 +1,7 @@
 use serde_json::{json};
 use std::process::Stdio;
-use crate::{models::branch::{FileContentResponse, FileMetadataResponse}, utils::git_command_async};
+use tokio::io::AsyncReadExt;
+use crate::{models::branch::{CheckoutPreview, CheckoutResult, FileContentResponse, FileMetadataResponse}, utils::{git_command_async, run_git_async}};
 use base64::{Engine as _, engine::general_purpose};
 
 #[tauri::command]
@@ -124,6 +125,146 @@ pub async fn checkout_branch(repo_path: String, branch: String) -> Result<String
     }
 }
 
+/// Lista os arquivos sujos e quais deles seriam sobrescritos ao trocar para `target`.
+#[tauri::command]
+pub async fn preview_checkout(repo_path: String, target: String) -> Result<CheckoutPreview, String> {
+    let dirty_files = list_dirty_files(&repo_path).await?;
+    let changed = run_git_async(&repo_path, &["diff", "--name-only", "-z", "HEAD", &target, "--"]).await?;
+    let changed: Vec<&str> = changed.split('\0').filter(|s| !s.is_empty()).collect();
+
+    let conflicting_files = dirty_files
+        .iter()
+        .filter(|f| changed.contains(&f.as_str()))
+        .cloned()
+        .collect();
+
+    Ok(CheckoutPreview {
+        is_local_branch: is_local_branch(&repo_path, &target).await,
+        target,
+        dirty_files,
+        conflicting_files,
+    })
+}
+
+/// Checkout de branch, tag ou commit tratando as alterações locais.
+/// `mode`: "carry" (mantém as alterações), "stash" (guarda e reaplica), "discard" ou "abort".
+#[tauri::command]
+pub async fn checkout_target(
+    repo_path: String,
+    target: String,
+    mode: String,
+    detach: bool,
+) -> Result<CheckoutResult, String> {
+    let preview = preview_checkout(repo_path.clone(), target.clone()).await?;
+    let has_changes = !preview.dirty_files.is_empty();
+
+    let mut result = CheckoutResult {
+        success: false,
+        head: String::new(),
+        detached: false,
+        stashed: false,
+        stash_kept: false,
+        conflicting_files: preview.conflicting_files.clone(),
+        reapply_conflicts: Vec::new(),
+        message: String::new(),
+    };
+
+    let mut args = vec!["checkout"];
+    match mode.as_str() {
+        "abort" => {
+            if !preview.conflicting_files.is_empty() {
+                result.message = "As alterações locais seriam sobrescritas pelo checkout".into();
+                return Ok(result);
+            }
+        }
+        // Leva as alterações junto; arquivos em conflito passam por merge de 3 vias
+        "carry" => {
+            if !preview.conflicting_files.is_empty() {
+                args.push("--merge");
+            }
+        }
+        "discard" => args.push("--force"),
+        "stash" => {
+            if has_changes {
+                let stash_message = format!("auto-stash antes do checkout de {}", target);
+                run_git_async(&repo_path, &["stash", "push", "-u", "-m", &stash_message]).await?;
+                result.stashed = true;
+            }
+        }
+        _ => return Err("Modo inválido. Use carry, stash, discard ou abort.".into()),
+    }
+
+    // Tags e commits sempre resultam em detached HEAD
+    if detach || !preview.is_local_branch {
+        args.push("--detach");
+    }
+    args.push(&target);
+
+    if let Err(err) = run_git_async(&repo_path, &args).await {
+        if result.stashed {
+            // Checkout falhou: devolve as alterações para onde estavam
+            let _ = run_git_async(&repo_path, &["stash", "pop"]).await;
+        }
+        return Err(err);
+    }
+
+    if result.stashed && run_git_async(&repo_path, &["stash", "pop"]).await.is_err() {
+        result.stash_kept = true;
+    }
+
+    if result.stash_kept || args.contains(&"--merge") {
+        let unmerged = run_git_async(&repo_path, &["diff", "--name-only", "-z", "--diff-filter=U"]).await.unwrap_or_default();
+        result.reapply_conflicts = unmerged.split('\0').filter(|s| !s.is_empty()).map(String::from).collect();
+    }
+
+    let head = run_git_async(&repo_path, &["rev-parse", "--abbrev-ref", "HEAD"]).await?.trim().to_string();
+    result.detached = head == "HEAD";
+    result.head = if result.detached {
+        run_git_async(&repo_path, &["rev-parse", "--short", "HEAD"]).await?.trim().to_string()
+    } else {
+        head
+    };
+    result.success = true;
+    result.message = if result.reapply_conflicts.is_empty() {
+        format!("Checkout de {} concluído", target)
+    } else {
+        "Checkout concluído, mas a reaplicação das alterações gerou conflitos".into()
+    };
+
+    Ok(result)
+}
+
+/// Cria uma branch a partir do HEAD atual (útil após checkout de tag ou commit).
+#[tauri::command]
+pub async fn create_branch_from_head(repo_path: String, branch_name: String) -> Result<String, String> {
+    run_git_async(&repo_path, &["checkout", "-b", &branch_name]).await?;
+    Ok(branch_name)
+}
+
+async fn list_dirty_files(repo_path: &str) -> Result<Vec<String>, String> {
+    let raw = run_git_async(repo_path, &["status", "--porcelain", "-z", "--untracked-files=all"]).await?;
+    let mut files = Vec::new();
+    let mut entries = raw.split('\0').filter(|s| !s.is_empty());
+
+    while let Some(entry) = entries.next() {
+        if entry.len() < 4 {
+            continue;
+        }
+        // Renomeações/cópias trazem o caminho de origem na entrada seguinte
+        if entry.starts_with('R') || entry.starts_with('C') {
+            entries.next();
+        }
+        files.push(entry[3..].to_string());
+    }
+
+    Ok(files)
+}
+
+async fn is_local_branch(repo_path: &str, name: &str) -> bool {
+    let reference = format!("refs/heads/{}", name);
+    run_git_async(repo_path, &["show-ref", "--verify", "--quiet", &reference]).await.is_ok()
+}
+
 #[tauri::command]
 pub async fn create_branch(
     repo_path: String, 
@@ -260,48 +401,85 @@ pub async fn list_branch_files_with_size(path: String, branch: String) -> Result
     Ok(files)
 }
 
+
+/// Tamanho máximo (em bytes) de texto enviado por página para o frontend.
+const PREVIEW_PAGE_BYTES: usize = 512 * 1024;
+/// Quantos bytes inspecionamos para detectar binários (mesmo limite usado pelo Git).
+const BINARY_SNIFF_BYTES: usize = 8000;
+
 #[tauri::command]
 pub async fn get_branch_file_content(
     path: String, 
     branch: String, 
-    file_path: String
+    file_path: String,
+    offset: Option<usize>,
 ) -> Result<FileContentResponse, String> {
     let target = format!("{}:{}", branch, file_path);
+    let size = blob_size(&path, &target).await?;
 
-    let output = git_command_async(&path)
-        .args(["show", &target])
-        .output()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if !output.status.success() {
-        return Err(format!("Erro ao ler arquivo: {}", String::from_utf8_lossy(&output.stderr)));
-    }
+    if let Some(mime) = image_mime_type(&file_path) {
+        let output = git_command_async(&path)
+            .args(["show", &target])
+            .output()
+            .await
+            .map_err(|e| e.to_string())?;
 
-    let raw_bytes = &output.stdout;
-    let size = raw_bytes.len();
-    let ext = file_path.split('.').last().unwrap_or("").to_lowercase();
-    let is_image = matches!(ext.as_str(), "png" | "jpg" | "jpeg" | "ico" | "gif" | "webp");
+        if !output.status.success() {
+            return Err(format!("Erro ao ler arquivo: {}", String::from_utf8_lossy(&output.stderr)));
+        }
 
-    if is_image {
-        let b64 = general_purpose::STANDARD.encode(raw_bytes);
-        Ok(FileContentResponse {
+        let b64 = general_purpose::STANDARD.encode(&output.stdout);
+        return Ok(FileContentResponse {
             is_image: true,
-            content: format!("data:image/{};base64,{}", ext, b64),
+            is_binary: mime != "image/svg+xml",
+            content: format!("data:{};base64,{}", mime, b64),
             size,
             line_count: None,
-        })
-    } else {
-        let content = String::from_utf8_lossy(raw_bytes).to_string();
-        let line_count = Some(content.lines().count());
-
-        Ok(FileContentResponse {
-            is_image: false,
-            content,
-            size,
-            line_count,
-        })
+            mime_type: Some(mime.to_string()),
+            encoding: None,
+            truncated: false,
+            next_offset: None,
+        });
     }
+
+    // O offset é em bytes do blob, então cada página lê só o próprio trecho.
+    // As linhas são contadas apenas na primeira página, que precisa percorrer o blob inteiro.
+    let offset = offset.unwrap_or(0).min(size);
+    let sample = read_blob(&path, &target, offset, PREVIEW_PAGE_BYTES, offset == 0).await?;
+
+    let encoding = match detect_encoding(&sample.head, &sample.page) {
+        Some(encoding) if !attributes_mark_binary(&path, &branch, &file_path).await => encoding,
+        _ => {
+            return Ok(FileContentResponse {
+                is_image: false,
+                is_binary: true,
+                content: String::new(),
+                size,
+                line_count: None,
+                mime_type: Some("application/octet-stream".to_string()),
+                encoding: None,
+                truncated: false,
+                next_offset: None,
+            });
+        }
+    };
+
+    let has_more = offset + sample.page.len() < size;
+    let end = if has_more { page_end(&sample.page, encoding) } else { sample.page.len() };
+    let bom = if offset == 0 { bom_len(encoding).min(end) } else { 0 };
+    let next_offset = has_more.then_some(offset + end);
+
+    Ok(FileContentResponse {
+        is_image: false,
+        is_binary: false,
+        content: decode_as(&sample.page[bom..end], encoding),
+        size,
+        line_count: sample.line_count(encoding, size),
+        mime_type: Some("text/plain".to_string()),
+        encoding: Some(encoding.to_string()),
+        truncated: next_offset.is_some(),
+        next_offset,
+    })
 }
 
 #[tauri::command]
@@ -320,16 +498,6 @@ pub async fn get_file_metadata(
         return Err("Arquivo não encontrado ou erro no git".into());
     }
 
-    let output = git_command_async(&path)
-        .args(["ls-tree", "-l", &branch, &file_path])
-        .output()
-        .await
-        .map_err(|e| e.to_string())?;
-
-    if !output.status.success() {
-        return Err("Arquivo não encontrado ou erro no git".into());
-    }
-
     let stdout = String::from_utf8_lossy(&output.stdout);
     let line = stdout.lines().next().ok_or("Arquivo não encontrado")?;
     
@@ -341,8 +509,256 @@ pub async fn get_file_metadata(
 
     let size = parts[3].parse::<usize>().unwrap_or(0);
 
+    // Basta o início do blob para decidir se é binário e qual o encoding
+    let sample = read_blob(&path, &format!("{}:{}", branch, file_path), 0, 0, false).await?;
+    let encoding = detect_encoding(&sample.head, &[]);
+    let is_binary = encoding.is_none() || attributes_mark_binary(&path, &branch, &file_path).await;
+
     Ok(FileMetadataResponse {
         size,
-        is_binary: true,
+        is_binary,
+        encoding: if is_binary { None } else { encoding.map(str::to_string) },
     })
-}
\ No newline at end of file
+}
+
+async fn blob_size(path: &str, target: &str) -> Result<usize, String> {
+    let output = git_command_async(path)
+        .args(["cat-file", "-s", target])
+        .output()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(format!("Erro ao ler arquivo: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .map_err(|_| "Tamanho do arquivo inválido".to_string())
+}
+
+/// Trecho de um blob lido sem carregar o arquivo inteiro na memória
+struct BlobSample {
+    /// Primeiros BINARY_SNIFF_BYTES bytes (BOM e detecção de binário)
+    head: Vec<u8>,
+    /// Bytes a partir do offset pedido, até o tamanho da página
+    page: Vec<u8>,
+    /// Quebras de linha no blob inteiro para cada forma de "\n": 8 bits, UTF-16LE e UTF-16BE
+    newlines: Option<[usize; 3]>,
+    /// Últimos dois bytes do blob, para saber se a última linha termina com quebra
+    tail: Vec<u8>,
+}
+
+impl BlobSample {
+    fn line_count(&self, encoding: &str, size: usize) -> Option<usize> {
+        let newlines = self.newlines?;
+        let (count, newline): (usize, &[u8]) = match encoding {
+            "utf-16le" => (newlines[1], &[b'\n', 0]),
+            "utf-16be" => (newlines[2], &[0, b'\n']),
+            _ => (newlines[0], b"\n"),
+        };
+        // Mesmo critério de `str::lines`: o texto após a última quebra conta como linha
+        let unterminated = size > bom_len(encoding) && !self.tail.ends_with(newline);
+        Some(count + usize::from(unterminated))
+    }
+}
+
+/// Lê o blob com `git cat-file` guardando só o início e a página pedida.
+/// Sem contagem de linhas a leitura para (e o processo é encerrado) assim que a página fica completa.
+async fn read_blob(path: &str, target: &str, offset: usize, page_len: usize, count_lines: bool) -> Result<BlobSample, String> {
+    let mut child = git_command_async(path)
+        .args(["cat-file", "blob", target])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    let mut stdout = child.stdout.take().ok_or("Não foi possível ler o arquivo")?;
+
+    let page_stop = offset.saturating_add(page_len);
+    let mut sample = BlobSample { head: Vec::new(), page: Vec::new(), newlines: None, tail: Vec::new() };
+    let mut newlines = [0usize; 3];
+    let mut previous: Option<u8> = None;
+    let mut position = 0usize;
+    let mut buffer = vec![0u8; 64 * 1024];
+
+    loop {
+        let read = stdout.read(&mut buffer).await.map_err(|e| e.to_string())?;
+        if read == 0 {
+            break;
+        }
+        let chunk = &buffer[..read];
+        let chunk_start = position;
+        position += read;
+
+        if sample.head.len() < BINARY_SNIFF_BYTES {
+            let take = (BINARY_SNIFF_BYTES - sample.head.len()).min(read);
+            sample.head.extend_from_slice(&chunk[..take]);
+        }
+
+        let (from, to) = (offset.max(chunk_start), page_stop.min(position));
+        if from < to {
+            sample.page.extend_from_slice(&chunk[from - chunk_start..to - chunk_start]);
+        }
+
+        if !count_lines {
+            if sample.head.len() >= BINARY_SNIFF_BYTES && position >= page_stop {
+                return Ok(sample);
+            }
+            continue;
+        }
+
+        for (i, &byte) in chunk.iter().enumerate() {
+            if byte == b'\n' {
+                newlines[0] += 1;
+            }
+            // Em UTF-16 cada unidade ocupa dois bytes alinhados a partir do início do blob
+            if (chunk_start + i) % 2 == 1 {
+                match (previous, byte) {
+                    (Some(b'\n'), 0) => newlines[1] += 1,
+                    (Some(0), b'\n') => newlines[2] += 1,
+                    _ => {}
+                }
+            }
+            previous = Some(byte);
+        }
+        sample.tail.extend_from_slice(&chunk[read.saturating_sub(2)..]);
+        let excess = sample.tail.len().saturating_sub(2);
+        sample.tail.drain(..excess);
+    }
+
+    let output = child.wait_with_output().await.map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(format!("Erro ao ler arquivo: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    if count_lines {
+        sample.newlines = Some(newlines);
+    }
+    Ok(sample)
+}
+
+fn image_mime_type(file_path: &str) -> Option<&'static str> {
+    let ext = file_path.rsplit('.').next().unwrap_or("").to_lowercase();
+    match ext.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        "ico" => Some("image/x-icon"),
+        "svg" => Some("image/svg+xml"),
+        "bmp" => Some("image/bmp"),
+        "avif" => Some("image/avif"),
+        _ => None,
+    }
+}
+
+/// Detecta o encoding a partir do início do blob e da página que será exibida.
+/// Retorna `None` quando o conteúdo parece binário (bytes NUL fora de UTF-16).
+fn detect_encoding(head: &[u8], page: &[u8]) -> Option<&'static str> {
+    if head.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        return Some("utf-8-bom");
+    }
+    if head.starts_with(&[0xFF, 0xFE]) {
+        return Some("utf-16le");
+    }
+    if head.starts_with(&[0xFE, 0xFF]) {
+        return Some("utf-16be");
+    }
+
+    if head.contains(&0) {
+        return None;
+    }
+
+    if is_utf8_prefix(head) && is_utf8_prefix(page) {
+        Some("utf-8")
+    } else {
+        Some("windows-1252")
+    }
+}
+
+/// UTF-8 válido, admitindo um caractere incompleto no fim (o trecho pode ter sido cortado ali)
+fn is_utf8_prefix(bytes: &[u8]) -> bool {
+    match std::str::from_utf8(bytes) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none(),
+    }
+}
+
+fn bom_len(encoding: &str) -> usize {
+    match encoding {
+        "utf-8-bom" => 3,
+        "utf-16le" | "utf-16be" => 2,
+        _ => 0,
+    }
+}
+
+/// Decodifica bytes já sem BOM no encoding detectado
+fn decode_as(bytes: &[u8], encoding: &str) -> String {
+    match encoding {
+        "utf-16le" => String::from_utf16_lossy(&bytes.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect::<Vec<u16>>()),
+        "utf-16be" => String::from_utf16_lossy(&bytes.chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect::<Vec<u16>>()),
+        "windows-1252" => bytes.iter().map(|&b| windows_1252_char(b)).collect(),
+        _ => String::from_utf8_lossy(bytes).to_string(),
+    }
+}
+
+/// Mapeia um byte Windows-1252 para o caractere Unicode correspondente.
+/// Fora da faixa 0x80..=0x9F o Windows-1252 coincide com o Latin-1.
+fn windows_1252_char(byte: u8) -> char {
+    const HIGH: [char; 32] = [
+        '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}',
+        '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ',
+    ];
+    match byte {
+        0x80..=0x9F => HIGH[(byte - 0x80) as usize],
+        _ => byte as char,
+    }
+}
+
+/// Respeita `binary` e `-diff` definidos no .gitattributes da própria ref.
+async fn attributes_mark_binary(path: &str, branch: &str, file_path: &str) -> bool {
+    let source = format!("--source={}", branch);
+    let mut output = git_command_async(path)
+        .args(["check-attr", &source, "binary", "diff", "--", file_path])
+        .output()
+        .await;
+
+    // `--source` só existe a partir do Git 2.40; nas versões antigas usamos o working tree
+    if !matches!(&output, Ok(o) if o.status.success()) {
+        output = git_command_async(path)
+            .args(["check-attr", "binary", "diff", "--", file_path])
+            .output()
+            .await;
+    }
+
+    match output {
+        Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout)
+            .lines()
+            .any(|line| line.ends_with(": binary: set") || line.ends_with(": diff: unset")),
+        _ => false,
+    }
+}
+
+/// Fim da página em bytes, cortado depois da última quebra de linha para que a próxima
+/// página comece numa linha inteira (e nunca no meio de um caractere)
+fn page_end(page: &[u8], encoding: &str) -> usize {
+    let end = match encoding {
+        "utf-16le" | "utf-16be" => {
+            let newline: [u8; 2] = if encoding == "utf-16le" { [b'\n', 0] } else { [0, b'\n'] };
+            let units = page.len() & !1;
+            (0..units).step_by(2).rev().find(|&i| page[i..i + 2] == newline).map_or(units, |i| i + 2)
+        }
+        _ => match page.iter().rposition(|&b| b == b'\n') {
+            Some(newline) => newline + 1,
+            None if encoding != "windows-1252" => match std::str::from_utf8(page) {
+                Err(e) if e.error_len().is_none() => e.valid_up_to(),
+                _ => page.len(),
+            },
+            None => page.len(),
+        },
+    };
+
+    if end == 0 { page.len() } else { end }
+}
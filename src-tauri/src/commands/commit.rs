@@ -1,10 +1,14 @@
-use serde::Serialize;
-use tauri::command;
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Emitter, Manager};
 use serde_json::{json, Value};
-use crate::utils::git_command;
-use rayon::prelude::*;
+use crate::{commands::{commit_index::refresh_index_if_present, commit_policy::{enforce_policy, join_message}, hooks::run_with_hooks}, graph::layout_rows, models::{author::CommitAuthor, commit_index::CommitTrailer, diff::DiffOptions, hook::GitCommandError, graph::{GraphRow, GraphState}, signature::{SignatureInfo, SigningOptions}}, utils::git_command};
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader, Write};
+use std::process::Stdio;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct Commit {
     hash: String,
     graph_symbol: Option<String>,
@@ -12,6 +16,57 @@ pub struct Commit {
     author: String,
     email: String,
     date: String,
+    #[serde(flatten)]
+    meta: CommitMeta,
+}
+
+/// Datas como Unix timestamp com o fuso de origem, e quem gravou o commit
+/// (difere do autor depois de rebase e cherry-pick).
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitMeta {
+    pub author_timestamp: i64,
+    pub author_tz_offset: i32, // Minutos em relação ao UTC
+    pub committer_name: String,
+    pub committer_email: String,
+    pub committer_timestamp: i64,
+    pub committer_tz_offset: i32,
+}
+
+// Campos lidos por `CommitMeta::parse`, nesta ordem
+const COMMIT_META_FORMAT: &str = "%at%x1f%ai%x1f%cn%x1f%ce%x1f%ct%x1f%ci";
+const COMMIT_META_FIELDS: usize = 6;
+
+impl CommitMeta {
+    fn parse(fields: &[&str]) -> Self {
+        let field = |i: usize| fields.get(i).copied().unwrap_or("");
+        CommitMeta {
+            author_timestamp: field(0).parse().unwrap_or(0),
+            author_tz_offset: tz_offset_minutes(field(1)),
+            committer_name: field(2).to_string(),
+            committer_email: field(3).to_string(),
+            committer_timestamp: field(4).parse().unwrap_or(0),
+            committer_tz_offset: tz_offset_minutes(field(5)),
+        }
+    }
+}
+
+/// Fuso de uma data ISO do git ("2024-05-01 14:30:00 -0300") em minutos
+fn tz_offset_minutes(iso_date: &str) -> i32 {
+    let tz = iso_date.rsplit(' ').next().unwrap_or("");
+    if tz.len() != 5 {
+        return 0;
+    }
+    let sign = if tz.starts_with('-') { -1 } else { 1 };
+    let hours: i32 = tz[1..3].parse().unwrap_or(0);
+    let minutes: i32 = tz[3..5].parse().unwrap_or(0);
+    sign * (hours * 60 + minutes)
+}
+
+#[derive(Serialize)]
+pub struct RecentAuthor {
+    name: String,
+    email: String,
 }
 
 #[derive(Serialize)]
@@ -24,6 +79,18 @@ pub struct FileEntry
     last_commit: Option<Commit>,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitFileChange {
+    file: String,             // Caminho atual (destino em renomeações)
+    status: String,           // A, M, D, R, C, T; no diff combinado uma letra por pai (ex: "MM")
+    old_path: Option<String>, // Origem de renomeações e cópias
+    similarity: Option<u32>,  // Porcentagem de similaridade de R/C
+    additions: Option<u32>,   // None para arquivos binários
+    deletions: Option<u32>,
+    binary: bool,
+}
+
 #[derive(Serialize, Clone)]
 pub struct GraphLine {
     pub graph_symbol: String,
@@ -35,15 +102,77 @@ pub struct GraphLine {
     pub date: String,
     pub ref_names: String,     // Nomes das refs (branches/tags)
     pub parent_hashes: String, // Hashes dos pais separados por espaço
+    pub signature: Option<SignatureInfo>,
+    #[serde(flatten)]
+    pub meta: CommitMeta,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LogFilter {
+    pub refs: Vec<String>,         // Vazio = HEAD
+    pub all: bool,                 // --all
+    pub offset: usize,
+    pub limit: Option<usize>,
+    pub author: Option<String>,
+    pub committer: Option<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub paths: Vec<String>,
+    pub message: Option<String>,   // --grep
+    pub pickaxe: Option<String>,   // -S (ou -G quando pickaxe_regex)
+    pub pickaxe_regex: bool,
+    pub merges_only: bool,
+    pub no_merges: bool,
+    pub first_parent: bool,
+    pub verify_signatures: bool,   // Consulta %G? (lento: chama o gpg/ssh-keygen por commit)
+}
+
+impl LogFilter {
+    /// Filtros que escondem commits sem reescrever os pais (%P continua apontando para commits
+    /// que nunca aparecem), o que deixaria colunas do grafo abertas para sempre.
+    fn hides_commits(&self) -> bool {
+        let set = |v: &Option<String>| v.as_ref().is_some_and(|v| !v.trim().is_empty());
+        set(&self.author)
+            || set(&self.committer)
+            || set(&self.message)
+            || self.pickaxe.as_ref().is_some_and(|p| !p.is_empty())
+            || self.merges_only
+            || self.no_merges
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CommitOptions {
+    pub signing: Option<SigningOptions>, // None = segue o commit.gpgSign do repositório
+    pub trailers: Vec<CommitTrailer>,
+    pub sign_off: bool,
+    pub no_verify: bool,
+    pub reset_author: bool,            // Só com amend: autor passa a ser a identidade atual
+    pub author: Option<CommitAuthor>,
+    pub date: Option<String>,          // Data do autor em qualquer formato aceito pelo git
+    pub no_edit: bool,                 // Só com amend: mantém a mensagem do commit
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitPage {
+    pub commits: Vec<GraphLine>,
+    pub rows: Vec<GraphRow>,              // Layout do grafo, um item por commit
+    pub graph_state: Option<GraphState>,  // Enviar de volta ao pedir a próxima página
+    pub offset: usize,
+    pub next_offset: Option<usize>,
 }
 
 #[tauri::command]
 pub fn list_commits(path: String, branch: String) -> Result<Vec<GraphLine>, String> {
+    let format = format!("--pretty=format:SEP%H%x1f%an%x1f%ae%x1f%ad%x1f%s%x1f%P%x1f%D%x1f{}", COMMIT_META_FORMAT);
     let output = git_command(&path)
         .args(&[
             "log",
             "--graph",
-            "--pretty=format:SEP%H|%an|%ae|%ad|%s|%P|%D",
+            &format,
             &branch,
             "--"
         ])
@@ -60,7 +189,7 @@ pub fn list_commits(path: String, branch: String) -> Result<Vec<GraphLine>, Stri
                 let parts: Vec<&str> = line.splitn(2, "SEP").collect();
                 let graph_part = parts[0].to_string();
                 let data_part = parts[1];
-                let data: Vec<&str> = data_part.splitn(7, '|').collect();
+                let data: Vec<&str> = data_part.split('\x1f').collect();
 
                 Some(GraphLine {
                     graph_symbol: graph_part,
@@ -72,6 +201,8 @@ pub fn list_commits(path: String, branch: String) -> Result<Vec<GraphLine>, Stri
                     message: data.get(4).unwrap_or(&"").to_string(),
                     parent_hashes: data.get(5).unwrap_or(&"").to_string(),
                     ref_names: data.get(6).unwrap_or(&"").to_string(),
+                    signature: None,
+                    meta: CommitMeta::parse(data.get(7..).unwrap_or(&[])),
                 })
             } else {
                 // Linha sem commit (apenas barras, espaços, etc.) -> Ignorada!
@@ -85,51 +216,184 @@ pub fn list_commits(path: String, branch: String) -> Result<Vec<GraphLine>, Stri
 
 #[tauri::command]
 pub fn list_user_commits(path: String, branch: String, email: String) -> Result<Vec<Commit>, String> {
-    let author_filter = format!("--author={}", email);
+    let filter = LogFilter {
+        refs: vec![branch],
+        author: Some(email),
+        ..Default::default()
+    };
 
-    let output = git_command(&path)
-        .args(&[
-            "log", 
-            "--pretty=format:%H|%an|%ae|%ad|%s", 
-            &branch, 
-            &author_filter,
-            "--"
-        ])
+    let page = match query_log(&path, &filter) {
+        Ok(page) => page,
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    let commits = page.commits
+        .into_iter()
+        .map(|line| Commit {
+            hash: line.hash,
+            graph_symbol: None,
+            author: line.author,
+            email: line.email,
+            date: line.date,
+            message: line.message,
+            meta: line.meta,
+        })
+        .collect();
+
+    Ok(commits)
+}
+
+/// Histórico paginado e filtrado. O grafo é calculado aqui em colunas em vez do ASCII do `--graph`;
+/// para continuar o layout, envie o `graph_state` da página anterior. Com filtros de autor, mensagem,
+/// pickaxe ou merges o grafo não é calculado (`rows` vazio), pois os pais não seriam contíguos.
+#[tauri::command]
+pub fn list_commits_page(path: String, filter: LogFilter, graph_state: Option<GraphState>) -> Result<CommitPage, String> {
+    let mut page = query_log(&path, &filter)?;
+    if filter.hides_commits() {
+        return Ok(page);
+    }
+    let mut state = graph_state.filter(|_| filter.offset > 0).unwrap_or_default();
+
+    page.rows = layout_rows(
+        page.commits.iter().map(|c| (c.hash.as_str(), c.parent_hashes.split_whitespace().collect())),
+        &mut state,
+    );
+    page.graph_state = Some(state);
+
+    Ok(page)
+}
+
+fn query_log(path: &str, filter: &LogFilter) -> Result<CommitPage, String> {
+    // Com limite zero nenhuma página avançaria e a paginação nunca terminaria
+    if filter.limit == Some(0) {
+        return Err("O limite de commits por página deve ser maior que zero".into());
+    }
+
+    let mut format = format!("--pretty=format:%H%x1f%an%x1f%ae%x1f%ad%x1f%s%x1f%P%x1f%D%x1f{}", COMMIT_META_FORMAT);
+    if filter.verify_signatures {
+        format.push_str("%x1f%G?%x1f%GS%x1f%GF");
+    }
+
+    let mut args: Vec<String> = vec![
+        "log".into(),
+        "-z".into(),
+        "--date-order".into(),
+        format,
+    ];
+
+    if filter.offset > 0 {
+        args.push(format!("--skip={}", filter.offset));
+    }
+    // Busca um commit a mais para saber se existe próxima página
+    if let Some(limit) = filter.limit {
+        args.push(format!("--max-count={}", limit.saturating_add(1)));
+    }
+
+    let optional_flags = [
+        ("--author=", &filter.author),
+        ("--committer=", &filter.committer),
+        ("--since=", &filter.since),
+        ("--until=", &filter.until),
+        ("--grep=", &filter.message),
+    ];
+    for (flag, value) in optional_flags {
+        if let Some(v) = value.as_ref().filter(|v| !v.trim().is_empty()) {
+            args.push(format!("{}{}", flag, v));
+        }
+    }
+
+    if let Some(p) = filter.pickaxe.as_ref().filter(|p| !p.is_empty()) {
+        args.push(format!("{}{}", if filter.pickaxe_regex { "-G" } else { "-S" }, p));
+    }
+    if filter.merges_only {
+        args.push("--merges".into());
+    }
+    if filter.no_merges {
+        args.push("--no-merges".into());
+    }
+    if filter.first_parent {
+        args.push("--first-parent".into());
+    }
+    // Com caminhos, --parents liga a reescrita: %P passa a apontar para o ancestral mais próximo listado
+    if !filter.paths.is_empty() {
+        args.push("--parents".into());
+    }
+
+    if filter.all {
+        args.push("--all".into());
+    } else if filter.refs.is_empty() {
+        args.push("HEAD".into());
+    } else {
+        args.extend(filter.refs.iter().cloned());
+    }
+
+    args.push("--".into());
+    args.extend(filter.paths.iter().cloned());
+
+    let output = git_command(path)
+        .args(&args)
         .output()
         .map_err(|e| e.to_string())?;
 
     if !output.status.success() {
-        return Ok(Vec::new());
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
     }
 
+    // Campos de assinatura vêm depois dos fixos e dos de CommitMeta
+    const SIGNATURE_FIELD: usize = 7 + COMMIT_META_FIELDS;
+
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let commits: Vec<Commit> = stdout
-        .lines()
-        .filter_map(|line| {
-            let parts: Vec<&str> = line.splitn(5, '|').collect();
-            if parts.len() < 5 { return None; }
-            
-            Some(Commit {
-                hash: parts[0].to_string(),
-                graph_symbol: None,
-                author: parts[1].to_string(),
-                email: parts[2].to_string(),
-                date: parts[3].to_string(),
-                message: parts[4].to_string(),
-            })
+    let mut commits: Vec<GraphLine> = stdout
+        .split('\0')
+        .filter(|record| !record.trim().is_empty())
+        .map(|record| {
+            let data: Vec<&str> = record.trim_start_matches('\n').split('\x1f').collect();
+            GraphLine {
+                graph_symbol: String::new(),
+                is_commit: true,
+                hash: data.first().unwrap_or(&"").to_string(),
+                author: data.get(1).unwrap_or(&"").to_string(),
+                email: data.get(2).unwrap_or(&"").to_string(),
+                date: data.get(3).unwrap_or(&"").to_string(),
+                message: data.get(4).unwrap_or(&"").to_string(),
+                // %P lista todos os pais mesmo com --first-parent
+                parent_hashes: match filter.first_parent {
+                    true => data.get(5).and_then(|p| p.split_whitespace().next()).unwrap_or("").to_string(),
+                    false => data.get(5).unwrap_or(&"").to_string(),
+                },
+                ref_names: data.get(6).unwrap_or(&"").to_string(),
+                signature: data.get(SIGNATURE_FIELD).map(|code| {
+                    SignatureInfo::from_git(code, data.get(SIGNATURE_FIELD + 1).unwrap_or(&""), data.get(SIGNATURE_FIELD + 2).unwrap_or(&""))
+                }),
+                meta: CommitMeta::parse(data.get(7..).unwrap_or(&[])),
+            }
         })
         .collect();
 
-    Ok(commits)
+    let next_offset = match filter.limit {
+        Some(limit) if commits.len() > limit => {
+            commits.truncate(limit);
+            Some(filter.offset.saturating_add(limit))
+        }
+        _ => None,
+    };
+
+    Ok(CommitPage {
+        commits,
+        rows: Vec::new(),
+        graph_state: None,
+        offset: filter.offset,
+        next_offset,
+    })
 }
 
+/// Detalhes de um commit. Commits de merge mostram o diff contra o pai escolhido
+/// (1 = primeiro pai, padrão) ou, com `combined`, os arquivos do diff combinado contra todos os pais;
+/// nesse modo `additions`/`deletions` ainda são contados contra o primeiro pai.
 #[command]
-pub fn get_commit_details(path: String, hash: String) -> Result<Value, String> {
+pub fn get_commit_details(path: String, hash: String, parent: Option<usize>, combined: Option<bool>) -> Result<Value, String> {
     let output = git_command(&path)
-        .arg("show")
-        .arg("--name-status") 
-        .arg("--pretty=format:%H%n%an%n%ae%n%ad%n%s%n%b%n%P")
-        .arg(&hash)
+        .args(["log", "-1", &format!("--format=%H%x00%an%x00%ae%x00%ad%x00%s%x00%b%x00%P%x00{}", COMMIT_META_FORMAT.replace("%x1f", "%x00")), &hash])
         .output()
         .map_err(|e| e.to_string())?;
 
@@ -138,118 +402,407 @@ pub fn get_commit_details(path: String, hash: String) -> Result<Value, String> {
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let lines: Vec<&str> = stdout.split('\n').collect();
-
-    let commit_hash = lines.get(0).unwrap_or(&"").to_string();
-    let author_name = lines.get(1).unwrap_or(&"").to_string();
-    let author_email = lines.get(2).unwrap_or(&"").to_string();
-    let author_date = lines.get(3).unwrap_or(&"").to_string();
-    let subject = lines.get(4).unwrap_or(&"").to_string();
-
-    let mut body_lines = Vec::new();
-    let mut idx = 5;
-    while idx < lines.len() {
-        let line = lines[idx];
-        if line.len() == 40 || (line.contains(' ') && line.split_whitespace().all(|h| h.len() == 40)) {
-            break; 
-        }
-        body_lines.push(line);
-        idx += 1;
-    }
-    let body = body_lines.join("\n").trim().to_string();
+    let fields: Vec<&str> = stdout.split('\0').collect();
+    let field = |i: usize| fields.get(i).map(|f| f.trim()).unwrap_or("").to_string();
 
-    let parents_line = lines.get(idx).unwrap_or(&"");
-    let parents: Vec<String> = parents_line
-        .split_whitespace()
-        .map(|p| p.to_string())
-        .collect();
+    let commit_hash = field(0);
+    let meta = CommitMeta::parse(&fields.get(7..).unwrap_or(&[]).iter().map(|f| f.trim()).collect::<Vec<_>>());
+    let parents: Vec<String> = field(6).split_whitespace().map(String::from).collect();
 
-    let mut files: Vec<Value> = Vec::new();
-    for line in lines.iter().skip(idx + 1) {
-        let trimmed = line.trim();
-        if trimmed.is_empty() { continue; }
-
-        let parts: Vec<&str> = trimmed.split('\t').collect();
-        
-        if parts.len() >= 2 {
-            let clean_path = parts[1].trim_matches('"').trim();
-
-            files.push(json!({
-                "file": clean_path,   
-                "status": parts[0].trim(),
-                "changes": ""
-            }));
-        }
-    }
+    let trailers = read_trailers(&path, &hash);
+    let body = strip_trailer_block(&field(5), &trailers);
+
+    let combined = combined.unwrap_or(false) && parents.len() > 1;
+    let (files, diff_parent) = if combined {
+        let raw = run_diff_tree(&path, &["-c", "--raw", &commit_hash])?;
+        // O diff-tree não tem numstat combinado: com --cc as contagens são contra o primeiro pai
+        let numstat = run_diff_tree(&path, &["--cc", "--numstat", &commit_hash])?;
+        (parse_file_changes(&format!("{}\0{}", raw, numstat)), None)
+    } else if parents.is_empty() {
+        (parse_file_changes(&run_diff_tree(&path, &["-M", "-C", "--raw", "--numstat", "--root", &commit_hash])?), None)
+    } else {
+        let index = parent.unwrap_or(1);
+        let base = parents
+            .get(index.wrapping_sub(1))
+            .ok_or_else(|| format!("O commit não tem o pai {}", index))?;
+        let changes = parse_file_changes(&run_diff_tree(&path, &["-M", "-C", "--raw", "--numstat", base, &commit_hash])?);
+        (changes, Some(base.clone()))
+    };
+
+    let signature = read_signature(&path, &commit_hash);
 
     Ok(json!({
         "hash": commit_hash,
-        "authorName": author_name,
-        "authorEmail": author_email,
-        "authorDate": author_date,
-        "subject": subject,
+        "authorName": field(1),
+        "authorEmail": field(2),
+        "authorDate": field(3),
+        "authorTimestamp": meta.author_timestamp,
+        "authorTzOffset": meta.author_tz_offset,
+        "committerName": meta.committer_name,
+        "committerEmail": meta.committer_email,
+        "committerTimestamp": meta.committer_timestamp,
+        "committerTzOffset": meta.committer_tz_offset,
+        "subject": field(4),
         "body": body,
         "parents": parents,
+        "signature": signature,
+        "trailers": trailers,
+        "diffParent": diff_parent,
+        "combined": combined,
         "files": files
     }))
 }
 
-#[command]
+fn run_diff_tree(path: &str, args: &[&str]) -> Result<String, String> {
+    let output = git_command(path)
+        .args(["diff-tree", "-r", "-z", "--no-commit-id"])
+        .args(args)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Junta a saída `--raw` (status e caminhos) com a `--numstat` (linhas alteradas), ambas com `-z`.
+/// As estatísticas são associadas pelo caminho novo do arquivo.
+fn parse_file_changes(output: &str) -> Vec<CommitFileChange> {
+    let mut files = Vec::new();
+    let mut stats: HashMap<String, (Option<u32>, Option<u32>, bool)> = HashMap::new();
+    let mut tokens = output.split('\0').filter(|t| !t.is_empty());
+
+    while let Some(token) = tokens.next() {
+        if let Some(raw) = token.strip_prefix(':') {
+            // ":<modos> <shas> <status>"; no diff combinado começa com "::" e tem uma letra por pai
+            let is_combined = raw.starts_with(':');
+            let status_field = raw.split_whitespace().last().unwrap_or("");
+            let letter = status_field.chars().next().unwrap_or('M');
+
+            let copied_or_renamed = !is_combined && matches!(letter, 'R' | 'C');
+            let old_path = if copied_or_renamed { tokens.next().map(String::from) } else { None };
+            let file = match tokens.next() {
+                Some(f) => f.to_string(),
+                None => break,
+            };
+
+            files.push(CommitFileChange {
+                file,
+                status: if is_combined { status_field.to_string() } else { letter.to_string() },
+                old_path,
+                similarity: if copied_or_renamed { status_field[1..].parse().ok() } else { None },
+                additions: None,
+                deletions: None,
+                binary: false,
+            });
+        } else {
+            // "<add>\t<del>\t<caminho>"; em renomeações o caminho vem vazio seguido de origem e destino
+            let mut parts = token.splitn(3, '\t');
+            let added = parts.next().unwrap_or("");
+            let deleted = parts.next().unwrap_or("");
+            let mut file = parts.next().unwrap_or("").to_string();
+            if file.is_empty() {
+                tokens.next();
+                file = tokens.next().unwrap_or("").to_string();
+            }
+            stats.insert(file, (added.parse().ok(), deleted.parse().ok(), added == "-"));
+        }
+    }
+
+    for change in &mut files {
+        if let Some((additions, deletions, binary)) = stats.get(&change.file) {
+            change.additions = *additions;
+            change.deletions = *deletions;
+            change.binary = *binary;
+        }
+    }
+    files
+}
+
+/// Roda fora da thread principal: hooks de pre-commit podem demorar
+#[command(async)]
 pub fn git_commit(
+    app: AppHandle,
     repo_path: String,
     message: String,
     description: String,
     amend: bool,
-) -> Result<String, String> {
-    // Mensagem final: se tiver descrição, junta com "\n\n"
-    let mut full_message = message;
-    if !description.trim().is_empty() {
-        full_message.push_str("\n\n");
-        full_message.push_str(&description);
+    options: Option<CommitOptions>,
+) -> Result<String, GitCommandError> {
+    let options = options.unwrap_or_default();
+    if !amend && (options.no_edit || options.reset_author) {
+        return Err(GitCommandError::from("Manter a mensagem ou redefinir o autor só é possível ao emendar um commit".to_string()));
+    }
+
+    let mut args: Vec<String> = signing_config_args(options.signing.as_ref());
+    args.push("commit".into());
+
+    if options.no_edit {
+        // Mensagem atual do commit; só reescrita se algum trailer for adicionado
+        let current = git_command(&repo_path)
+            .args(["log", "-1", "--format=%B", "HEAD"])
+            .output()
+            .map_err(|e| e.to_string())?;
+        let current = String::from_utf8_lossy(&current.stdout).trim_end().to_string();
+        let full_message = apply_trailers(&repo_path, &current, &options.trailers)?;
+        if full_message.trim_end() == current {
+            args.push("--no-edit".into());
+        } else {
+            enforce_policy(&repo_path, &full_message)?;
+            args.extend(["-m".to_string(), full_message]);
+        }
+    } else {
+        // Mensagem final: se tiver descrição, junta com "\n\n"
+        let full_message = join_message(&message, &description);
+        let full_message = apply_trailers(&repo_path, &full_message, &options.trailers)?;
+        enforce_policy(&repo_path, &full_message)?;
+        args.extend(["-m".to_string(), full_message]);
     }
 
-    let mut args = vec!["commit", "-m", &full_message];
     if amend {
-        args.push("--amend");
+        args.push("--amend".into());
+    }
+    if options.reset_author {
+        args.push("--reset-author".into());
+    }
+    if let Some(author) = &options.author {
+        if author.name.trim().is_empty() || author.email.trim().is_empty() {
+            return Err(GitCommandError::from("Informe o nome e o e-mail do autor".to_string()));
+        }
+        args.push(format!("--author={} <{}>", author.name.trim(), author.email.trim()));
+    }
+    if let Some(date) = options.date.as_ref().filter(|d| !d.trim().is_empty()) {
+        args.push(format!("--date={}", date.trim()));
+    }
+    if options.sign_off {
+        args.push("--signoff".into());
+    }
+    if options.no_verify {
+        args.push("--no-verify".into());
+    }
+    // Sem opções explícitas vale o commit.gpgSign do repositório
+    if let Some(signing) = &options.signing {
+        args.push(signing_flag(signing));
     }
 
-    let output = git_command(&repo_path)
-        .args(&args)
+    let mut cmd = git_command(&repo_path);
+    cmd.args(&args);
+    let output = run_with_hooks(&app, "commit", cmd)?;
+
+    if output.success {
+        refresh_index_if_present(&repo_path);
+        Ok(output.stdout)
+    } else {
+        Err(output.into_error())
+    }
+}
+
+/// Autores recentes (mais recentes primeiro) para sugerir `Co-authored-by`.
+#[tauri::command]
+pub fn list_recent_authors(path: String, limit: Option<usize>) -> Result<Vec<RecentAuthor>, String> {
+    let output = git_command(&path)
+        .args(["log", "--max-count=1000", "--format=%an%x1f%ae", "HEAD"])
         .output()
         .map_err(|e| e.to_string())?;
 
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut seen = std::collections::HashSet::new();
+    let authors = stdout
+        .lines()
+        .filter_map(|line| line.split_once('\x1f'))
+        .filter(|(_, email)| seen.insert(email.to_lowercase()))
+        .take(limit.unwrap_or(20))
+        .map(|(name, email)| RecentAuthor { name: name.to_string(), email: email.to_string() })
+        .collect();
+
+    Ok(authors)
+}
+
+/// Adiciona trailers com a mesma semântica do `git interpret-trailers` (sem duplicar iguais).
+fn apply_trailers(repo_path: &str, message: &str, trailers: &[CommitTrailer]) -> Result<String, String> {
+    let trailers: Vec<&CommitTrailer> = trailers
+        .iter()
+        .filter(|t| !t.key.trim().is_empty() && !t.value.trim().is_empty())
+        .collect();
+    if trailers.is_empty() {
+        return Ok(message.to_string());
+    }
+
+    let mut cmd = git_command(repo_path);
+    cmd.args(["interpret-trailers", "--if-exists", "addIfDifferent"]);
+    for trailer in trailers {
+        cmd.arg("--trailer").arg(format!("{}: {}", trailer.key.trim(), trailer.value.trim()));
+    }
+
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // Sem a quebra final o git considera a última linha parte do bloco de trailers
+        stdin.write_all(format!("{}\n", message.trim_end()).as_bytes()).map_err(|e| e.to_string())?;
+    }
+
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
     if output.status.success() {
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        Ok(String::from_utf8_lossy(&output.stdout).trim_end().to_string())
     } else {
         Err(String::from_utf8_lossy(&output.stderr).to_string())
     }
 }
 
+fn read_trailers(path: &str, hash: &str) -> Vec<CommitTrailer> {
+    let output = git_command(path)
+        .args(["log", "-1", "--format=%(trailers:only,unfold)", hash])
+        .output();
+
+    match output {
+        Ok(o) if o.status.success() => CommitTrailer::parse_block(&String::from_utf8_lossy(&o.stdout)),
+        _ => Vec::new(),
+    }
+}
+
+/// Remove do corpo o último parágrafo quando ele é o bloco de trailers
+fn strip_trailer_block(body: &str, trailers: &[CommitTrailer]) -> String {
+    if trailers.is_empty() {
+        return body.to_string();
+    }
+
+    let (rest, last) = body.rsplit_once("\n\n").unwrap_or(("", body));
+    let is_trailer_block = last
+        .lines()
+        .all(|line| line.starts_with(char::is_whitespace) || trailers.iter().any(|t| line.starts_with(&format!("{}:", t.key))));
+
+    if is_trailer_block {
+        rest.trim_end().to_string()
+    } else {
+        body.to_string()
+    }
+}
+
+/// `-c gpg.format=...` e `-c user.signingKey=...` para escolher o tipo de assinatura por comando.
+pub(crate) fn signing_config_args(signing: Option<&SigningOptions>) -> Vec<String> {
+    let mut args = Vec::new();
+    let signing = match signing {
+        Some(s) if s.sign => s,
+        _ => return args,
+    };
+
+    if let Some(format) = signing.format.as_ref().filter(|f| !f.trim().is_empty()) {
+        let format = if format == "gpg" { "openpgp" } else { format.as_str() };
+        args.push("-c".into());
+        args.push(format!("gpg.format={}", format));
+    }
+    if let Some(key) = signing.key.as_ref().filter(|k| !k.trim().is_empty()) {
+        args.push("-c".into());
+        args.push(format!("user.signingKey={}", key.trim()));
+    }
+
+    args
+}
+
+fn signing_flag(signing: &SigningOptions) -> String {
+    if signing.sign { "--gpg-sign".into() } else { "--no-gpg-sign".into() }
+}
+
+fn read_signature(path: &str, hash: &str) -> SignatureInfo {
+    let output = git_command(path)
+        .args(["log", "-1", "--format=%G?%x1f%GS%x1f%GF", hash])
+        .output();
+
+    match output {
+        Ok(o) if o.status.success() => {
+            let stdout = String::from_utf8_lossy(&o.stdout);
+            let parts: Vec<&str> = stdout.trim_end().split('\x1f').collect();
+            SignatureInfo::from_git(
+                parts.first().unwrap_or(&"N"),
+                parts.get(1).unwrap_or(&""),
+                parts.get(2).unwrap_or(&""),
+            )
+        }
+        _ => SignatureInfo::from_git("N", "", ""),
+    }
+}
+
+/// Diff de um arquivo no commit contra o primeiro pai (ou contra a árvore vazia no commit raiz).
+/// Em merges, `parent` recebe o `diffParent` de `get_commit_details` para comparar com o mesmo pai
+/// da lista de arquivos. Informe `old_path` para que renomeações apareçam como tal.
 #[tauri::command]
-pub async fn get_commit_file_diff(repo_path: String, commit_sha: String, file_path: String) -> Result<serde_json::Value, String> {
-    let diff_output = git_command(&repo_path)
-        .arg("diff")
-        .arg(format!("{}^!", commit_sha)) 
-        .arg("--")
-        .arg(&file_path)
-        .output()
-        .map_err(|e| e.to_string())?;
+pub async fn get_commit_file_diff(
+    repo_path: String,
+    commit_sha: String,
+    file_path: String,
+    old_path: Option<String>,
+    parent: Option<String>,
+    options: Option<DiffOptions>,
+) -> Result<serde_json::Value, String> {
+    let options = options.unwrap_or_default().to_args();
+    let mut cmd = git_command(&repo_path);
+    match parent.as_ref().filter(|p| !p.trim().is_empty()) {
+        Some(parent) => cmd.arg("diff").args(options).args([parent, &commit_sha]),
+        None => cmd.args(["show", "--format=", "--diff-merges=first-parent"]).args(options).arg(&commit_sha),
+    };
+    cmd.arg("--");
+    if let Some(old) = old_path.as_ref().filter(|p| **p != file_path) {
+        cmd.arg(old);
+    }
+    let diff_output = cmd.arg(&file_path).output().map_err(|e| e.to_string())?;
+
+    if !diff_output.status.success() {
+        return Err(String::from_utf8_lossy(&diff_output.stderr).to_string());
+    }
 
     let diff_text = String::from_utf8_lossy(&diff_output.stdout).to_string();
 
     Ok(serde_json::json!({
         "diff": diff_text,
-        "oldFile": file_path,
+        "oldFile": old_path.unwrap_or_else(|| file_path.clone()),
         "newFile": file_path
     }))
 }
 
+/// Diff entre duas refs quaisquer, opcionalmente limitado a um caminho
+#[tauri::command]
+pub fn get_ref_diff(
+    repo_path: String,
+    from: String,
+    to: String,
+    file_path: Option<String>,
+    options: Option<DiffOptions>,
+) -> Result<serde_json::Value, String> {
+    let mut cmd = git_command(&repo_path);
+    cmd.arg("diff").args(options.unwrap_or_default().to_args()).args([&from, &to, "--"]);
+    if let Some(file) = file_path.as_ref().filter(|f| !f.trim().is_empty()) {
+        cmd.arg(file);
+    }
+    let output = cmd.output().map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    Ok(json!({
+        "diff": String::from_utf8_lossy(&output.stdout).to_string(),
+        "from": from,
+        "to": to
+    }))
+}
+
 #[tauri::command]
 pub fn get_last_commit_for_path(path: String, branch: String, file_path: String) -> Result<Option<Commit>, String> {
+    let format = commit_line_format();
     let mut args = vec![
         "log", 
         "-n", "1", 
-        "--pretty=format:%H|%an|%ae|%ad|%s", 
+        &format, 
         &branch
     ];
 
@@ -268,21 +821,7 @@ pub fn get_last_commit_for_path(path: String, branch: String, file_path: String)
         return Ok(None); 
     }
 
-    let line = stdout.lines().next().unwrap_or("");
-    let parts: Vec<&str> = line.splitn(5, '|').collect();
-    
-    if parts.len() < 5 { 
-        return Ok(None); 
-    }
-
-    Ok(Some(Commit {
-        hash: parts[0].to_string(),
-        author: parts[1].to_string(),
-        email: parts[2].to_string(),
-        date: parts[3].to_string(),
-        message: parts[4].to_string(),
-        graph_symbol: None,
-    }))
+    Ok(stdout.lines().next().and_then(parse_commit_line))
 }
 
 #[tauri::command]
@@ -290,7 +829,7 @@ pub fn get_path_history(path: String, branch: String, file_path: String) -> Resu
     let output = git_command(&path)
         .args(&[
             "log", 
-            "--pretty=format:%H|%an|%ae|%ad|%s", 
+            &commit_line_format(), 
             "--follow",
             &branch,
             "--", 
@@ -302,39 +841,66 @@ pub fn get_path_history(path: String, branch: String, file_path: String) -> Resu
     let stdout = String::from_utf8_lossy(&output.stdout);
     let commits = stdout
         .lines()
-        .filter_map(|line| {
-            let parts: Vec<&str> = line.splitn(5, '|').collect();
-            if parts.len() < 5 { return None; }
-            Some(Commit {
-                hash: parts[0].to_string(),
-                author: parts[1].to_string(),
-                email: parts[2].to_string(),
-                date: parts[3].to_string(),
-                message: parts[4].to_string(),
-                graph_symbol: None,
-            })
-        })
+        .filter_map(parse_commit_line)
         .collect();
 
     Ok(commits)
 }
 
+fn commit_line_format() -> String {
+    format!("--pretty=format:{}", commit_line_fields())
+}
+
+// Assunto por último: é o único campo livre da linha
+fn commit_line_fields() -> String {
+    format!("%H%x1f%an%x1f%ae%x1f%ad%x1f{}%x1f%s", COMMIT_META_FORMAT)
+}
+
+fn parse_commit_line(line: &str) -> Option<Commit> {
+    let parts: Vec<&str> = line.splitn(5 + COMMIT_META_FIELDS, '\x1f').collect();
+    if parts.len() < 5 + COMMIT_META_FIELDS {
+        return None;
+    }
+
+    Some(Commit {
+        hash: parts[0].to_string(),
+        author: parts[1].to_string(),
+        email: parts[2].to_string(),
+        date: parts[3].to_string(),
+        message: parts[4 + COMMIT_META_FIELDS].to_string(),
+        graph_symbol: None,
+        meta: CommitMeta::parse(&parts[4..4 + COMMIT_META_FIELDS]),
+    })
+}
+
+/// Lista um diretório da branch com o último commit de cada entrada.
+/// Sem cache, as entradas voltam sem commit e o histórico é percorrido uma única vez em segundo
+/// plano; os commits chegam pelo evento "directory-commits" conforme são resolvidos.
 #[tauri::command]
 pub fn list_directory_with_commits(
+    app: AppHandle,
     repo_path: String, 
     branch: String, 
     folder_path: String
 ) -> Result<Vec<FileEntry>, String> {
-    let target_path = if folder_path.is_empty() || folder_path == "." {
-        format!("{}:", branch)
-    } else {
-        let path_with_slash = if folder_path.ends_with('/') { folder_path.clone() } else { format!("{}/", folder_path) };
-        format!("{}:{}", branch, path_with_slash)
-    };
+    let folder = folder_path.trim_matches('/');
+    let folder = if folder == "." { "" } else { folder }.to_string();
+
+    // Resolve a branch uma vez: o cache e a busca usam o mesmo commit
+    let head_output = git_command(&repo_path)
+        .args(["rev-parse", "--verify", &format!("{}^{{commit}}", branch)])
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !head_output.status.success() {
+        return Err(String::from_utf8_lossy(&head_output.stderr).to_string());
+    }
+    let head = String::from_utf8_lossy(&head_output.stdout).trim().to_string();
 
-    // 1. Lista os arquivos (LS-TREE) - Rápido
+    let target_path = if folder.is_empty() { format!("{}:", head) } else { format!("{}:{}/", head, folder) };
+
+    // 1. Lista os arquivos (LS-TREE) - "<modo> <tipo> <objeto>\t<nome>"
     let output = git_command(&repo_path)
-        .args(&["ls-tree", "-l", &target_path])
+        .args(["ls-tree", "-z", &target_path])
         .output()
         .map_err(|e| e.to_string())?;
 
@@ -342,50 +908,217 @@ pub fn list_directory_with_commits(
         return Err(String::from_utf8_lossy(&output.stderr).to_string());
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    
-    // 2. Coleta as linhas em um Vec para podermos paralelizar
-    let lines: Vec<&str> = stdout.lines().collect();
-
-    // 3. PROCESSAMENTO PARALELO (O segredo da velocidade)
-    let entries: Vec<FileEntry> = lines.into_par_iter().map(|line| {
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() < 5 { return None; }
-
-        let is_dir = parts[1] == "tree";
-        let name = parts[4..].join(" ");
-
-        let full_item_path = if folder_path.is_empty() || folder_path == "." {
-            name.clone()
+    // Sem resultado completo em cache, uma busca já em andamento para a mesma chave é
+    // compartilhada: a entrada recebe o que já foi resolvido e o restante chega pelos eventos dela
+    let cache_key = (repo_path.clone(), head.clone(), folder.clone());
+    let (cached, start_walk) = {
+        let mut cache = directory_commit_cache().lock().map_err(|e| e.to_string())?;
+        if let Some(complete) = cache.complete.get(&cache_key) {
+            (Some(complete.clone()), false)
+        } else if let Some(walk) = cache.in_flight.get_mut(&cache_key) {
+            walk.branches.insert(branch.clone());
+            (Some(walk.resolved.clone()), false)
         } else {
-            format!("{}/{}", folder_path.trim_end_matches('/'), name)
-        };
-
-        // Agora cada thread busca o seu commit de forma independente
-        let last_commit = get_last_commit_for_path(
-            repo_path.clone(), 
-            branch.clone(), 
-            full_item_path.clone()
-        ).unwrap_or(None);
-
-        Some(FileEntry {
-            name,
-            path: full_item_path,
-            is_dir,
-            last_commit,
+            let walk = cache.in_flight.entry(cache_key.clone()).or_default();
+            walk.branches.insert(branch.clone());
+            (None, true)
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut entries: Vec<FileEntry> = stdout
+        .split('\0')
+        .filter_map(|record| {
+            let (info, name) = record.split_once('\t')?;
+            let full_item_path = if folder.is_empty() { name.to_string() } else { format!("{}/{}", folder, name) };
+            Some(FileEntry {
+                name: name.to_string(),
+                path: full_item_path,
+                is_dir: info.split(' ').nth(1) == Some("tree"),
+                last_commit: cached.as_ref().and_then(|c| c.get(name).cloned()),
+            })
         })
-    })
-    .filter_map(|x| x) // Remove os Nones
-    .collect();
+        .collect();
 
-    // 4. Ordenação final
-    let mut final_entries = entries;
-    final_entries.sort_by(|a, b| {
+    // 2. Ordenação final
+    entries.sort_by(|a, b| {
         if a.is_dir != b.is_dir {
             return b.is_dir.cmp(&a.is_dir); 
         }
         a.name.to_lowercase().cmp(&b.name.to_lowercase())
     });
 
-    Ok(final_entries)
-}
\ No newline at end of file
+    // 3. Sem cache: resolve todas as entradas em uma única passada pelo histórico
+    if start_walk {
+        let names: HashSet<String> = entries.iter().map(|e| e.name.clone()).collect();
+        std::thread::spawn(move || walk_directory_history(&app, &cache_key, names));
+    }
+
+    Ok(entries)
+}
+
+const DIRECTORY_CACHE_LIMIT: usize = 256;
+const DIRECTORY_STREAM_INTERVAL: Duration = Duration::from_millis(100);
+
+// (repositório, commit da branch, diretório)
+type DirectoryKey = (String, String, String);
+
+#[derive(Default)]
+struct DirectoryCommitCache {
+    /// Buscas que chegaram ao fim: nome da entrada → último commit
+    complete: HashMap<DirectoryKey, HashMap<String, Commit>>,
+    /// Buscas em andamento, compartilhadas por pedidos simultâneos da mesma chave
+    in_flight: HashMap<DirectoryKey, DirectoryWalk>,
+}
+
+#[derive(Default)]
+struct DirectoryWalk {
+    resolved: HashMap<String, Commit>,
+    /// Nomes de branch usados nos pedidos; os eventos saem para cada um deles
+    branches: HashSet<String>,
+}
+
+/// Commits não mudam, então um resultado em cache nunca fica desatualizado
+fn directory_commit_cache() -> &'static Mutex<DirectoryCommitCache> {
+    static CACHE: OnceLock<Mutex<DirectoryCommitCache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(DirectoryCommitCache::default()))
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryEntryCommit {
+    name: String,
+    last_commit: Commit,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryCommitsBatch {
+    repo_path: String,
+    branch: String,
+    folder_path: String,
+    entries: Vec<DirectoryEntryCommit>,
+    done: bool,
+}
+
+/// Percorre `git log --name-only` do diretório do mais novo para o mais antigo; o primeiro commit
+/// que toca uma entrada é o último commit dela. Para assim que todas forem resolvidas.
+/// Só vai para o cache o resultado de uma busca que chegou ao fim.
+fn walk_directory_history(app: &AppHandle, key: &DirectoryKey, mut pending: HashSet<String>) {
+    let (repo_path, head, folder) = key;
+    let window = app.get_webview_window("main");
+    let emit = |entries: Vec<DirectoryEntryCommit>, done: bool, branches: &HashSet<String>| {
+        if let Some(window) = &window {
+            for branch in branches {
+                let _ = window.emit("directory-commits", DirectoryCommitsBatch {
+                    repo_path: repo_path.to_string(),
+                    branch: branch.to_string(),
+                    folder_path: folder.to_string(),
+                    entries: entries.clone(),
+                    done,
+                });
+            }
+        }
+    };
+    // Registra o commit resolvido na busca em andamento e devolve quem está aguardando
+    let record = |name: Option<(&str, &Commit)>| -> HashSet<String> {
+        match directory_commit_cache().lock() {
+            Ok(mut cache) => match cache.in_flight.get_mut(key) {
+                Some(walk) => {
+                    if let Some((name, commit)) = name {
+                        walk.resolved.insert(name.to_string(), commit.clone());
+                    }
+                    walk.branches.clone()
+                }
+                None => HashSet::new(),
+            },
+            Err(_) => HashSet::new(),
+        }
+    };
+
+    let pathspec = if folder.is_empty() { ".".to_string() } else { folder.to_string() };
+    let prefix = if folder.is_empty() { String::new() } else { format!("{}/", folder) };
+
+    let child = git_command(repo_path)
+        // -c lista em merges os arquivos que diferem de todos os pais, como a simplificação do `git log -- <caminho>`
+        .args(["log", "-z", "-c", "--no-renames", "--name-only"])
+        .arg(format!("--pretty=format:%x1e{}", commit_line_fields()))
+        .args([head.as_str(), "--", &pathspec])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn();
+
+    let mut batch = Vec::new();
+    let mut complete = false;
+
+    if let Ok(mut child) = child {
+        let mut last_emit = Instant::now();
+        let mut current: Option<Commit> = None;
+
+        if let Some(stdout) = child.stdout.take() {
+            let mut read_all = true;
+            // Cada commit é "\x1e<campos>\n<caminho>" seguido dos demais caminhos, separados por NUL
+            for token in BufReader::new(stdout).split(0) {
+                let Ok(token) = token else {
+                    read_all = false;
+                    break;
+                };
+                let token = String::from_utf8_lossy(&token);
+                let token = token.trim_start_matches('\n');
+
+                let path = match token.strip_prefix('\x1e') {
+                    Some(record_line) => {
+                        let (header, first_path) = record_line.split_once('\n').unwrap_or((record_line, ""));
+                        current = parse_commit_line(header);
+                        if !batch.is_empty() && last_emit.elapsed() >= DIRECTORY_STREAM_INTERVAL {
+                            emit(std::mem::take(&mut batch), false, &record(None));
+                            last_emit = Instant::now();
+                        }
+                        first_path
+                    }
+                    None => token,
+                };
+
+                let (commit, rest) = match (&current, path.strip_prefix(prefix.as_str())) {
+                    (Some(commit), Some(rest)) => (commit, rest),
+                    _ => continue,
+                };
+                let name = rest.split('/').next().unwrap_or(rest);
+                if pending.remove(name) {
+                    record(Some((name, commit)));
+                    batch.push(DirectoryEntryCommit { name: name.to_string(), last_commit: commit.clone() });
+                    if pending.is_empty() {
+                        break;
+                    }
+                }
+            }
+
+            if pending.is_empty() {
+                complete = true;
+                let _ = child.kill();
+                let _ = child.wait();
+            } else {
+                // Entradas sem commit (clone raso) só contam como resolvidas se o log terminou bem
+                complete = read_all && child.wait().is_ok_and(|status| status.success());
+            }
+        } else {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+
+    let branches = match directory_commit_cache().lock() {
+        Ok(mut cache) => {
+            let walk = cache.in_flight.remove(key).unwrap_or_default();
+            if complete {
+                if cache.complete.len() >= DIRECTORY_CACHE_LIMIT {
+                    cache.complete.clear();
+                }
+                cache.complete.insert(key.clone(), walk.resolved);
+            }
+            walk.branches
+        }
+        Err(_) => HashSet::new(),
+    };
+    emit(batch, true, &branches);
+}
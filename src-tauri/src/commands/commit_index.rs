@@ -0,0 +1,378 @@
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use crate::{models::commit_index::{CommitIndexStatus, CommitTrailer, IndexedCommit}, utils::git_command};
+
+const INDEX_DIR: &str = "devbrook";
+const INDEX_FILE: &str = "commit-index.jsonl";
+const TIPS_FILE: &str = "commit-index-tips";
+const DEFAULT_SEARCH_LIMIT: usize = 200;
+
+/// Índices já carregados do disco, por caminho do repositório
+fn loaded_indexes() -> &'static Mutex<HashMap<String, Vec<IndexedCommit>>> {
+    static INDEXES: OnceLock<Mutex<HashMap<String, Vec<IndexedCommit>>>> = OnceLock::new();
+    INDEXES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Uma trava por repositório: atualizações simultâneas (commit, pull e fetch) leriam as mesmas
+/// pontas já indexadas e gravariam os mesmos commits duas vezes.
+fn repo_lock(path: &str) -> Result<Arc<Mutex<()>>, String> {
+    static LOCKS: OnceLock<Mutex<HashMap<String, Arc<Mutex<()>>>>> = OnceLock::new();
+    let mut locks = LOCKS.get_or_init(|| Mutex::new(HashMap::new())).lock().map_err(|e| e.to_string())?;
+    Ok(locks.entry(path.to_string()).or_default().clone())
+}
+
+/// Apaga e recria o índice (necessário quando o histórico é reescrito).
+#[tauri::command(async)]
+pub fn rebuild_commit_index(path: String) -> Result<CommitIndexStatus, String> {
+    let lock = repo_lock(&path)?;
+    let _guard = lock.lock().map_err(|e| e.to_string())?;
+
+    clear_index(&path)?;
+    update_index_locked(&path)
+}
+
+#[tauri::command(async)]
+pub fn update_commit_index(path: String) -> Result<CommitIndexStatus, String> {
+    update_index(&path)
+}
+
+/// Busca no índice. Aceita texto livre e os filtros `author:`, `path:`, `before:` e `after:` (AAAA-MM-DD).
+#[tauri::command(async)]
+pub fn search_commit_index(path: String, query: String, limit: Option<usize>) -> Result<Vec<IndexedCommit>, String> {
+    let dir = index_dir(&path)?;
+    if !dir.join(INDEX_FILE).exists() {
+        update_index(&path)?;
+    }
+
+    let query = IndexQuery::parse(&query)?;
+    let limit = limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+
+    let mut indexes = loaded_indexes().lock().map_err(|e| e.to_string())?;
+    if !indexes.contains_key(&path) {
+        indexes.insert(path.clone(), read_index(&dir.join(INDEX_FILE))?);
+    }
+
+    let mut results: Vec<IndexedCommit> = indexes[&path]
+        .iter()
+        .filter(|commit| query.matches(commit))
+        .cloned()
+        .collect();
+
+    results.sort_by_key(|c| std::cmp::Reverse(c.date));
+    results.truncate(limit);
+
+    Ok(results)
+}
+
+/// Indexa apenas os commits que ainda não são alcançáveis pelas refs já indexadas.
+/// Chamado após fetch, pull e commit quando o índice já existe.
+pub fn update_index(path: &str) -> Result<CommitIndexStatus, String> {
+    let lock = repo_lock(path)?;
+    let _guard = lock.lock().map_err(|e| e.to_string())?;
+    update_index_locked(path)
+}
+
+fn update_index_locked(path: &str) -> Result<CommitIndexStatus, String> {
+    let dir = index_dir(path)?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let mut known_tips: Vec<String> = fs::read_to_string(dir.join(TIPS_FILE))
+        .unwrap_or_default()
+        .lines()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+    let current_tips = list_tips(path)?;
+
+    // Uma ponta indexada que sumiu (histórico reescrito e coletado pelo gc) faria o `git log` falhar
+    // sempre. Sem ela não dá para saber o que já está no índice, então ele é refeito do zero.
+    if !known_tips.is_empty() && !all_commits_exist(path, &known_tips)? {
+        clear_index(path)?;
+        known_tips.clear();
+    }
+
+    let mut child = git_command(path)
+        .args([
+            "log",
+            "-z",
+            "--name-only",
+            "--format=%x1e%H%x1f%an%x1f%ae%x1f%at%x1f%s%x1f%b%x1f%(trailers:only,unfold)%x1f",
+            "--stdin",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    // Revisões pelo stdin: as novas pontas e, negadas, as já indexadas
+    if let Some(mut stdin) = child.stdin.take() {
+        let mut revs = String::new();
+        for tip in &current_tips {
+            revs.push_str(tip);
+            revs.push('\n');
+        }
+        for tip in &known_tips {
+            revs.push('^');
+            revs.push_str(tip);
+            revs.push('\n');
+        }
+        stdin.write_all(revs.as_bytes()).map_err(|e| e.to_string())?;
+    }
+
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(format!(
+            "Falha ao ler o histórico para o índice: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let new_commits: Vec<IndexedCommit> = stdout.split('\x1e').filter_map(parse_index_record).collect();
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(INDEX_FILE))
+        .map_err(|e| e.to_string())?;
+    for commit in &new_commits {
+        let line = serde_json::to_string(commit).map_err(|e| e.to_string())?;
+        writeln!(file, "{}", line).map_err(|e| e.to_string())?;
+    }
+    fs::write(dir.join(TIPS_FILE), current_tips.join("\n")).map_err(|e| e.to_string())?;
+
+    let added_commits = new_commits.len();
+    let mut indexes = loaded_indexes().lock().map_err(|e| e.to_string())?;
+    let indexed_commits = match indexes.get_mut(path) {
+        Some(cached) => {
+            cached.extend(new_commits);
+            cached.len()
+        }
+        None => BufReader::new(fs::File::open(dir.join(INDEX_FILE)).map_err(|e| e.to_string())?)
+            .lines()
+            .count(),
+    };
+
+    Ok(CommitIndexStatus { indexed_commits, added_commits })
+}
+
+/// Atualiza o índice somente se o usuário já o criou; erros são ignorados.
+pub fn refresh_index_if_present(path: &str) {
+    let exists = index_dir(path).map(|dir| dir.join(INDEX_FILE).exists()).unwrap_or(false);
+    if exists {
+        let _ = update_index(path);
+    }
+}
+
+fn clear_index(path: &str) -> Result<(), String> {
+    let dir = index_dir(path)?;
+    let _ = fs::remove_file(dir.join(INDEX_FILE));
+    let _ = fs::remove_file(dir.join(TIPS_FILE));
+    if let Ok(mut indexes) = loaded_indexes().lock() {
+        indexes.remove(path);
+    }
+    Ok(())
+}
+
+/// Confere com `cat-file --batch-check` se todos os hashes ainda existem como commits
+fn all_commits_exist(path: &str, hashes: &[String]) -> Result<bool, String> {
+    let mut child = git_command(path)
+        .args(["cat-file", "--batch-check=%(objecttype)"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    // O cat-file responde linha a linha: com muitas refs a escrita precisa correr junto com a leitura
+    let mut stdin = child.stdin.take().ok_or("Falha ao abrir a entrada do git cat-file")?;
+    let input = format!("{}\n", hashes.join("\n"));
+    let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    let _ = writer.join();
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    // Objetos ausentes aparecem como "<hash> missing"
+    Ok(String::from_utf8_lossy(&output.stdout).lines().all(|line| line.trim() == "commit"))
+}
+
+fn index_dir(path: &str) -> Result<PathBuf, String> {
+    let output = git_command(path)
+        .args(["rev-parse", "--git-common-dir"])
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let git_dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    let git_dir = if git_dir.is_absolute() { git_dir } else { Path::new(path).join(git_dir) };
+    Ok(git_dir.join(INDEX_DIR))
+}
+
+fn list_tips(path: &str) -> Result<Vec<String>, String> {
+    let output = git_command(path)
+        .args(["for-each-ref", "--format=%(objectname)", "refs/heads", "refs/remotes", "refs/tags"])
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let mut tips: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    tips.sort();
+    tips.dedup();
+    Ok(tips)
+}
+
+fn read_index(file: &Path) -> Result<Vec<IndexedCommit>, String> {
+    let reader = BufReader::new(fs::File::open(file).map_err(|e| e.to_string())?);
+    Ok(reader
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect())
+}
+
+/// Registro: "<campos separados por \x1f>\x1f\0\n<arquivo>\0<arquivo>\0..."
+fn parse_index_record(record: &str) -> Option<IndexedCommit> {
+    let (header, files) = record.rsplit_once('\x1f')?;
+    let fields: Vec<&str> = header.splitn(7, '\x1f').collect();
+    if fields.len() < 7 {
+        return None;
+    }
+
+    let trailers = CommitTrailer::parse_block(fields[6]);
+
+    let paths = files
+        .split('\0')
+        .map(|f| f.trim_matches('\n'))
+        .filter(|f| !f.is_empty())
+        .map(String::from)
+        .collect();
+
+    Some(IndexedCommit {
+        hash: fields[0].to_string(),
+        author: fields[1].to_string(),
+        email: fields[2].to_string(),
+        date: fields[3].parse::<i64>().unwrap_or(0),
+        subject: fields[4].to_string(),
+        body: fields[5].trim().to_string(),
+        trailers,
+        paths,
+    })
+}
+
+#[derive(Default)]
+struct IndexQuery {
+    terms: Vec<String>,
+    authors: Vec<String>,
+    paths: Vec<String>,
+    before: Option<i64>,
+    after: Option<i64>,
+}
+
+impl IndexQuery {
+    fn parse(query: &str) -> Result<Self, String> {
+        let mut parsed = IndexQuery::default();
+
+        for token in tokenize(query) {
+            let lower = token.to_lowercase();
+            match lower.split_once(':') {
+                Some(("author", value)) => parsed.authors.push(value.to_string()),
+                Some(("path", value)) => parsed.paths.push(value.to_string()),
+                Some(("before", value)) => parsed.before = Some(parse_date(value)?),
+                Some(("after", value)) => parsed.after = Some(parse_date(value)?),
+                _ => parsed.terms.push(lower),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    fn matches(&self, commit: &IndexedCommit) -> bool {
+        if self.before.is_some_and(|before| commit.date >= before)
+            || self.after.is_some_and(|after| commit.date < after)
+        {
+            return false;
+        }
+
+        let author = format!("{} {}", commit.author, commit.email).to_lowercase();
+        if !self.authors.iter().all(|a| author.contains(a)) {
+            return false;
+        }
+
+        if !self.paths.iter().all(|p| commit.paths.iter().any(|f| f.to_lowercase().contains(p))) {
+            return false;
+        }
+
+        if self.terms.is_empty() {
+            return true;
+        }
+
+        let mut text = format!("{}\n{}\n{}", commit.hash, commit.subject, commit.body);
+        for trailer in &commit.trailers {
+            text.push_str(&format!("\n{}: {}", trailer.key, trailer.value));
+        }
+        let text = text.to_lowercase();
+
+        self.terms.iter().all(|t| text.contains(t))
+    }
+}
+
+/// Separa por espaços respeitando trechos entre aspas: author:"Ana Souza"
+fn tokenize(query: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+
+    for c in query.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+/// Converte "AAAA-MM-DD" em timestamp Unix (meia-noite UTC)
+fn parse_date(value: &str) -> Result<i64, String> {
+    let parts: Vec<i64> = value.split('-').filter_map(|p| p.parse().ok()).collect();
+    if parts.len() != 3 {
+        return Err(format!("Data inválida: {} (use AAAA-MM-DD)", value));
+    }
+
+    let (y, m, d) = (parts[0], parts[1], parts[2]);
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * (m + if m > 2 { -3 } else { 9 }) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+
+    Ok(days * 86400)
+}
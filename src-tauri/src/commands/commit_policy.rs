@@ -0,0 +1,335 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use regex::{Regex, RegexBuilder};
+use crate::{models::commit_policy::{CommitPolicy, PolicyViolation, ViolationSeverity}, utils::git_command};
+
+const POLICY_FILE: &str = ".devbrook/commit-policy.json";
+const DEFAULT_TYPES: [&str; 11] = ["feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore", "revert"];
+
+/// Verbos comuns usados para detectar mensagens fora do imperativo ("adds", "fixed", "updating")
+const COMMON_VERBS: [&str; 28] = [
+    "add", "fix", "update", "remove", "change", "create", "implement", "improve", "refactor", "rename",
+    "move", "delete", "make", "use", "allow", "bump", "merge", "revert", "support", "handle", "set",
+    "show", "clean", "document", "prevent", "enable", "disable", "replace",
+];
+
+#[tauri::command]
+pub fn get_commit_policy(repo_path: String) -> Result<Option<CommitPolicy>, String> {
+    load_policy(&repo_path)
+}
+
+#[tauri::command]
+pub fn save_commit_policy(repo_path: String, policy: CommitPolicy) -> Result<(), String> {
+    if let Some(pattern) = &policy.ticket_pattern {
+        Regex::new(pattern).map_err(|e| format!("Padrão de ticket inválido: {}", e))?;
+    }
+
+    let file = Path::new(&repo_path).join(POLICY_FILE);
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(&policy).map_err(|e| e.to_string())?;
+    fs::write(file, content).map_err(|e| e.to_string())
+}
+
+/// Conteúdo do `commit.template` configurado, para pré-preencher a mensagem.
+#[tauri::command]
+pub fn get_commit_template(repo_path: String) -> Result<Option<String>, String> {
+    Ok(read_commit_template(&repo_path))
+}
+
+#[tauri::command]
+pub fn validate_commit_message(repo_path: String, message: String, description: String) -> Result<Vec<PolicyViolation>, String> {
+    match load_policy(&repo_path)? {
+        Some(policy) => Ok(validate(&repo_path, &policy, &join_message(&message, &description))),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Usado pelo `git_commit`: falha se a política do repositório tiver violações do tipo erro.
+pub fn enforce_policy(repo_path: &str, full_message: &str) -> Result<(), String> {
+    let policy = match load_policy(repo_path)? {
+        Some(policy) => policy,
+        None => return Ok(()),
+    };
+
+    let errors: Vec<String> = validate(repo_path, &policy, full_message)
+        .into_iter()
+        .filter(|v| v.severity == ViolationSeverity::Error)
+        .map(|v| format!("- {}", v.message))
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Mensagem de commit fora da política do repositório:\n{}", errors.join("\n")))
+    }
+}
+
+pub fn join_message(message: &str, description: &str) -> String {
+    let mut full_message = message.to_string();
+    if !description.trim().is_empty() {
+        full_message.push_str("\n\n");
+        full_message.push_str(description);
+    }
+    full_message
+}
+
+fn load_policy(repo_path: &str) -> Result<Option<CommitPolicy>, String> {
+    let file = Path::new(repo_path).join(POLICY_FILE);
+    if !file.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&file).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Erro ao ler {}: {}", POLICY_FILE, e))
+}
+
+fn validate(repo_path: &str, policy: &CommitPolicy, full_message: &str) -> Vec<PolicyViolation> {
+    let mut violations = Vec::new();
+    let lines: Vec<&str> = full_message.lines().collect();
+    let subject = lines.first().copied().unwrap_or("");
+
+    if subject.trim().is_empty() {
+        violations.push(violation("subject-empty", ViolationSeverity::Error, "O assunto do commit está vazio", 1, None));
+        return violations;
+    }
+
+    let mut description = subject;
+    if policy.conventional_commits.enabled {
+        match check_conventional(policy, subject, &mut violations) {
+            Some(rest) => description = rest,
+            None => return violations,
+        }
+    }
+
+    if let Some(max) = policy.subject_max_length {
+        let length = subject.chars().count();
+        if length > max {
+            violations.push(violation(
+                "subject-length",
+                ViolationSeverity::Error,
+                &format!("O assunto tem {} caracteres (máximo {})", length, max),
+                1,
+                Some((max, length)),
+            ));
+        }
+    }
+
+    if policy.imperative_mood {
+        let first_word = description.split_whitespace().next().unwrap_or("");
+        if !is_imperative(first_word) {
+            let start = subject.chars().count() - description.chars().count();
+            violations.push(violation(
+                "imperative-mood",
+                ViolationSeverity::Warning,
+                &format!("Use o imperativo no assunto (\"{}\" não parece imperativo)", first_word),
+                1,
+                Some((start, start + first_word.chars().count())),
+            ));
+        }
+    }
+
+    if let Some(pattern) = policy.ticket_pattern.as_ref().filter(|p| !p.trim().is_empty()) {
+        match Regex::new(pattern) {
+            Ok(re) if !re.is_match(full_message) => violations.push(violation(
+                "ticket-reference",
+                ViolationSeverity::Error,
+                &format!("A mensagem precisa referenciar um ticket ({})", pattern),
+                1,
+                None,
+            )),
+            Ok(_) => {}
+            Err(e) => violations.push(violation(
+                "ticket-reference",
+                ViolationSeverity::Error,
+                &format!("Padrão de ticket inválido na política: {}", e),
+                1,
+                None,
+            )),
+        }
+    }
+
+    for word in policy.forbidden_words.iter().filter(|w| !w.trim().is_empty()) {
+        let re = RegexBuilder::new(&format!(r"\b{}\b", regex::escape(word.trim())))
+            .case_insensitive(true)
+            .build();
+        let re = match re {
+            Ok(re) => re,
+            Err(_) => continue,
+        };
+
+        for (index, line) in lines.iter().enumerate() {
+            for found in re.find_iter(line) {
+                let start = line[..found.start()].chars().count();
+                violations.push(violation(
+                    "forbidden-word",
+                    ViolationSeverity::Error,
+                    &format!("Palavra proibida: \"{}\"", found.as_str()),
+                    index + 1,
+                    Some((start, start + found.as_str().chars().count())),
+                ));
+            }
+        }
+    }
+
+    if let Some(max) = policy.body_max_line_length {
+        for (index, line) in lines.iter().enumerate().skip(1) {
+            let length = line.chars().count();
+            // Linhas sem espaço (URLs, hashes) não podem ser quebradas
+            if length > max && line.trim().contains(' ') {
+                violations.push(violation(
+                    "body-line-length",
+                    ViolationSeverity::Warning,
+                    &format!("A linha {} tem {} caracteres (máximo {})", index + 1, length, max),
+                    index + 1,
+                    Some((max, length)),
+                ));
+            }
+        }
+    }
+
+    if lines.len() > 1 && !lines[1].trim().is_empty() {
+        violations.push(violation(
+            "blank-line-after-subject",
+            ViolationSeverity::Warning,
+            "Separe o assunto do corpo com uma linha em branco",
+            2,
+            None,
+        ));
+    }
+
+    if policy.use_commit_template {
+        if let Some(template) = read_commit_template(repo_path) {
+            if strip_comments(&template) == strip_comments(full_message) {
+                violations.push(violation(
+                    "commit-template",
+                    ViolationSeverity::Error,
+                    "A mensagem é igual ao commit.template; preencha o modelo",
+                    1,
+                    None,
+                ));
+            }
+        }
+    }
+
+    violations
+}
+
+/// Valida "tipo(escopo)!: descrição" e devolve a descrição
+fn check_conventional<'a>(policy: &CommitPolicy, subject: &'a str, violations: &mut Vec<PolicyViolation>) -> Option<&'a str> {
+    let rule = &policy.conventional_commits;
+    let re = Regex::new(r"^(?P<type>[a-zA-Z]+)(?:\((?P<scope>[^()]*)\))?!?: (?P<desc>\S.*)$").ok()?;
+
+    let caps = match re.captures(subject) {
+        Some(caps) => caps,
+        None => {
+            violations.push(violation(
+                "conventional-format",
+                ViolationSeverity::Error,
+                "O assunto deve seguir o formato Conventional Commits: tipo(escopo): descrição",
+                1,
+                None,
+            ));
+            return None;
+        }
+    };
+
+    let commit_type = caps.name("type")?;
+    let allowed_type = if rule.types.is_empty() {
+        DEFAULT_TYPES.contains(&commit_type.as_str())
+    } else {
+        rule.types.iter().any(|t| t == commit_type.as_str())
+    };
+    if !allowed_type {
+        violations.push(violation(
+            "conventional-type",
+            ViolationSeverity::Error,
+            &format!("Tipo \"{}\" não permitido", commit_type.as_str()),
+            1,
+            Some((0, commit_type.as_str().chars().count())),
+        ));
+    }
+
+    match caps.name("scope") {
+        Some(scope) if !rule.scopes.is_empty() && !rule.scopes.iter().any(|s| s == scope.as_str()) => {
+            let start = subject[..scope.start()].chars().count();
+            violations.push(violation(
+                "conventional-scope",
+                ViolationSeverity::Error,
+                &format!("Escopo \"{}\" não permitido", scope.as_str()),
+                1,
+                Some((start, start + scope.as_str().chars().count())),
+            ));
+        }
+        None if rule.require_scope => violations.push(violation(
+            "conventional-scope",
+            ViolationSeverity::Error,
+            "Informe um escopo: tipo(escopo): descrição",
+            1,
+            None,
+        )),
+        _ => {}
+    }
+
+    caps.name("desc").map(|d| d.as_str())
+}
+
+fn is_imperative(word: &str) -> bool {
+    let word = word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+
+    // "added", "updated", "fixing", "updating", "fixes", "adds"
+    let conjugated_verb = [
+        word.strip_suffix("ed").map(String::from),
+        word.strip_suffix('d').map(String::from),
+        word.strip_suffix("ing").map(String::from),
+        word.strip_suffix("ing").map(|base| format!("{}e", base)),
+        word.strip_suffix("es").map(String::from),
+        word.strip_suffix('s').map(String::from),
+    ]
+    .into_iter()
+    .flatten()
+    .any(|base| COMMON_VERBS.contains(&base.as_str()));
+
+    let looks_past_or_gerund = word.chars().count() >= 6 && (word.ends_with("ed") || word.ends_with("ing"));
+
+    !(conjugated_verb || looks_past_or_gerund)
+}
+
+fn read_commit_template(repo_path: &str) -> Option<String> {
+    let output = git_command(repo_path)
+        .args(["config", "--get", "--path", "commit.template"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let configured = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    let file = if configured.is_absolute() { configured } else { Path::new(repo_path).join(configured) };
+    fs::read_to_string(file).ok()
+}
+
+fn strip_comments(message: &str) -> String {
+    message
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+fn violation(rule: &str, severity: ViolationSeverity, message: &str, line: usize, range: Option<(usize, usize)>) -> PolicyViolation {
+    PolicyViolation {
+        rule: rule.to_string(),
+        severity,
+        message: message.to_string(),
+        line,
+        start: range.map(|r| r.0),
+        end: range.map(|r| r.1),
+    }
+}
@@ -0,0 +1,176 @@
+use tauri::{Manager, AppHandle, Emitter};
+use std::env::temp_dir;
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use serde_json::Value;
+use crate::{models::hook::{GitCommandError, HookInfo, HookOutputEvent}, utils::git_command};
+
+const KNOWN_HOOKS: [&str; 24] = [
+    "applypatch-msg", "pre-applypatch", "post-applypatch", "pre-commit", "pre-merge-commit",
+    "prepare-commit-msg", "commit-msg", "post-commit", "pre-rebase", "post-checkout", "post-merge",
+    "pre-push", "pre-receive", "update", "proc-receive", "post-receive", "post-update",
+    "reference-transaction", "push-to-checkout", "pre-auto-gc", "post-rewrite", "sendemail-validate",
+    "fsmonitor-watchman", "post-index-change",
+];
+
+pub struct HookRunOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+    pub failed_hook: Option<String>,
+}
+
+impl HookRunOutput {
+    /// Converte uma execução malsucedida no erro correspondente
+    pub fn into_error(self) -> GitCommandError {
+        match self.failed_hook {
+            Some(hook) => GitCommandError::Hook {
+                hook,
+                message: format!("{}{}", self.stdout, self.stderr).trim().to_string(),
+            },
+            None => GitCommandError::Git { message: self.stderr },
+        }
+    }
+}
+
+/// Lista os hooks instalados, respeitando `core.hooksPath`.
+#[tauri::command]
+pub fn list_hooks(path: String) -> Result<Vec<HookInfo>, String> {
+    let (dir, from_hooks_path) = hooks_dir(&path)?;
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    let mut hooks: Vec<HookInfo> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            if !KNOWN_HOOKS.contains(&name.as_str()) {
+                return None;
+            }
+            Some(HookInfo {
+                executable: is_executable(&e.path()),
+                path: e.path().to_string_lossy().to_string(),
+                name,
+                from_hooks_path,
+            })
+        })
+        .collect();
+
+    hooks.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(hooks)
+}
+
+/// Executa um comando git que dispara hooks, emitindo cada linha de saída no evento `hook-output`.
+/// Usa o trace2 do Git para descobrir qual hook falhou.
+pub fn run_with_hooks(app: &AppHandle, operation: &str, mut cmd: Command) -> Result<HookRunOutput, String> {
+    let window = app.get_webview_window("main");
+    let trace_file = trace_file_path();
+
+    cmd.env("GIT_TRACE2_EVENT", &trace_file)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = cmd.spawn().map_err(|e| e.to_string())?;
+    let stdout = child.stdout.take().ok_or("Falha ao capturar a saída do git")?;
+    let stderr = child.stderr.take().ok_or("Falha ao capturar a saída do git")?;
+
+    let stream = |reader: Box<dyn Read + Send>, name: &str| {
+        let mut collected = String::new();
+        for line in BufReader::new(reader).lines().map_while(Result::ok) {
+            if let Some(w) = &window {
+                let _ = w.emit("hook-output", HookOutputEvent {
+                    operation: operation.into(),
+                    stream: name.into(),
+                    line: line.clone(),
+                });
+            }
+            collected.push_str(&line);
+            collected.push('\n');
+        }
+        collected
+    };
+
+    let (stdout, stderr) = thread::scope(|scope| {
+        let out = scope.spawn(|| stream(Box::new(stdout), "stdout"));
+        let err = stream(Box::new(stderr), "stderr");
+        (out.join().unwrap_or_default(), err)
+    });
+
+    let status = child.wait().map_err(|e| e.to_string())?;
+    let failed_hook = if status.success() { None } else { find_failed_hook(&trace_file) };
+    let _ = fs::remove_file(&trace_file);
+
+    Ok(HookRunOutput {
+        success: status.success(),
+        stdout,
+        stderr,
+        failed_hook,
+    })
+}
+
+fn hooks_dir(path: &str) -> Result<(PathBuf, bool), String> {
+    let hooks_path = git_command(path)
+        .args(["config", "--get", "--path", "core.hooksPath"])
+        .output()
+        .map_err(|e| e.to_string())?;
+    let from_hooks_path = hooks_path.status.success();
+
+    let output = git_command(path)
+        .args(["rev-parse", "--git-path", "hooks"])
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    let dir = if dir.is_absolute() { dir } else { Path::new(path).join(dir) };
+    Ok((dir, from_hooks_path))
+}
+
+#[cfg(unix)]
+fn is_executable(file: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(file).map(|m| m.permissions().mode() & 0o111 != 0).unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_file: &Path) -> bool {
+    // No Windows o Git executa qualquer hook presente (via sh)
+    true
+}
+
+fn trace_file_path() -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    temp_dir().join(format!("devbrook-trace2-{}-{}.json", std::process::id(), nanos))
+}
+
+/// Procura no trace2 um `child_start` de hook cujo `child_exit` tenha código diferente de zero.
+/// O par é casado por `sid` porque processos git filhos escrevem no mesmo arquivo.
+fn find_failed_hook(trace_file: &Path) -> Option<String> {
+    let content = fs::read_to_string(trace_file).ok()?;
+    let events: Vec<Value> = content.lines().filter_map(|l| serde_json::from_str(l).ok()).collect();
+
+    events
+        .iter()
+        .filter(|e| e["event"] == "child_start" && e["child_class"] == "hook")
+        .find(|start| {
+            events.iter().any(|exit| {
+                exit["event"] == "child_exit"
+                    && exit["sid"] == start["sid"]
+                    && exit["child_id"] == start["child_id"]
+                    && exit["code"].as_i64().is_some_and(|code| code != 0)
+            })
+        })
+        .and_then(|start| start["hook_name"].as_str().map(String::from))
+}
@@ -0,0 +1,286 @@
+use std::io::Write;
+use std::process::Stdio;
+use crate::{
+    models::hunk::{DiffSide, HunkPatchRequest, HunkSelection},
+    utils::{git_command, run_git},
+};
+
+/// Coloca no stage apenas os hunks/linhas escolhidos do diff working tree x índice
+#[tauri::command]
+pub fn stage_hunks(repo_path: String, request: HunkPatchRequest) -> Result<(), String> {
+    apply_selection(&repo_path, &request, PatchMode::Stage)
+}
+
+/// Tira do stage apenas os hunks/linhas escolhidos do diff índice x HEAD
+#[tauri::command]
+pub fn unstage_hunks(repo_path: String, request: HunkPatchRequest) -> Result<(), String> {
+    apply_selection(&repo_path, &request, PatchMode::Unstage)
+}
+
+/// Descarta do working tree apenas os hunks/linhas escolhidos (o que está no stage é mantido)
+#[tauri::command]
+pub fn discard_hunks(repo_path: String, request: HunkPatchRequest) -> Result<(), String> {
+    apply_selection(&repo_path, &request, PatchMode::Discard)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum PatchMode {
+    Stage,
+    Unstage,
+    Discard,
+}
+
+impl PatchMode {
+    /// Unstage e discard desfazem alterações: o patch é aplicado com --reverse
+    fn reverse(self) -> bool {
+        self != PatchMode::Stage
+    }
+}
+
+struct Hunk<'a> {
+    header: String, // Só a parte "@@ -a,b +c,d @@"
+    old_start: u32,
+    old_count: u32,
+    new_start: u32,
+    new_count: u32,
+    lines: Vec<&'a [u8]>,
+}
+
+/// Monta um patch mínimo a partir do diff atual e aplica com `git apply`, que recusa tudo se o
+/// contexto não bater. Blobs ou cabeçalhos diferentes dos do diff exibido abortam a operação.
+fn apply_selection(repo_path: &str, request: &HunkPatchRequest, mode: PatchMode) -> Result<(), String> {
+    if request.hunks.is_empty() {
+        return Err("Nenhum trecho selecionado".into());
+    }
+
+    // Arquivo não versionado: o intent-to-add faz o diff mostrá-lo como novo
+    let intent_to_add = mode == PatchMode::Stage
+        && !run_git(repo_path, &["ls-files", "--others", "--exclude-standard", "--", &request.file])?.trim().is_empty();
+    if intent_to_add {
+        run_git(repo_path, &["add", "--intent-to-add", "--", &request.file])?;
+    }
+
+    let result = apply_selection_to_diff(repo_path, request, mode);
+    if result.is_err() && intent_to_add {
+        let _ = run_git(repo_path, &["rm", "--cached", "-q", "--", &request.file]);
+    }
+    result
+}
+
+fn apply_selection_to_diff(repo_path: &str, request: &HunkPatchRequest, mode: PatchMode) -> Result<(), String> {
+    let context = format!("-U{}", request.context_lines.unwrap_or(3));
+    let mut cmd = git_command(repo_path);
+    cmd.args(["diff", "--no-color", "--no-ext-diff", "--no-textconv", "--src-prefix=a/", "--dst-prefix=b/", &context]);
+    if mode == PatchMode::Unstage {
+        cmd.arg("--cached");
+    }
+    let output = cmd.args(["--", &request.file]).output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    // Bytes em vez de String: "\r\n" e arquivos fora do UTF-8 precisam voltar intactos no patch
+    let (file_header, hunks) = parse_diff(&output.stdout)?;
+    let header_text: Vec<String> = file_header.iter().map(|l| String::from_utf8_lossy(l).trim_end().to_string()).collect();
+    if header_text.iter().any(|l| l.starts_with("Binary files") || l.starts_with("GIT binary patch")) {
+        return Err("Arquivos binários não podem ser aplicados por trecho".into());
+    }
+
+    // A linha "index <antigo>..<novo>" identifica o conteúdo dos dois lados: se o arquivo foi
+    // editado depois que o diff foi exibido, o blob muda mesmo que os cabeçalhos dos hunks não mudem
+    let stale = "O arquivo mudou desde que o diff foi calculado. Atualize o diff e tente novamente.";
+    let current_index = header_text.iter().find_map(|l| l.strip_prefix("index ")).and_then(|l| l.split_whitespace().next());
+    if !current_index.is_some_and(|current| same_blobs(current, &request.index)) {
+        return Err(stale.into());
+    }
+    if request.hunks.iter().any(|s| !hunks.iter().any(|h| h.header == header_of(&s.header))) {
+        return Err(stale.into());
+    }
+
+    let mut patch: Vec<u8> = file_header.concat();
+    let mut offset: i64 = 0;
+    let mut emitted = 0;
+
+    for hunk in &hunks {
+        let selection = match request.hunks.iter().find(|s| header_of(&s.header) == hunk.header) {
+            Some(selection) => selection,
+            None => continue,
+        };
+        if let Some((body, old_count, new_count)) = build_hunk(hunk, selection, mode.reverse()) {
+            // O lado de partida mantém a posição; o outro é deslocado pelos hunks anteriores
+            let (old_start, new_start) = if mode.reverse() {
+                let pos = side_position(hunk.new_start, hunk.new_count);
+                (start_for(pos - offset, old_count), start_for(pos, new_count))
+            } else {
+                let pos = side_position(hunk.old_start, hunk.old_count);
+                (start_for(pos, old_count), start_for(pos + offset, new_count))
+            };
+            offset += new_count as i64 - old_count as i64;
+            patch.extend_from_slice(format!("@@ -{},{} +{},{} @@\n", old_start, old_count, new_start, new_count).as_bytes());
+            patch.extend_from_slice(&body);
+            emitted += 1;
+        }
+    }
+
+    if emitted == 0 {
+        return Err("Nenhuma alteração nas linhas selecionadas".into());
+    }
+
+    let mut cmd = git_command(repo_path);
+    cmd.args(["apply", "--whitespace=nowarn"]);
+    if mode != PatchMode::Discard {
+        cmd.arg("--cached");
+    }
+    if mode.reverse() {
+        cmd.arg("--reverse");
+    }
+    let mut child = cmd
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(&patch).map_err(|e| e.to_string())?;
+    }
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!("{}\n{}", stale, String::from_utf8_lossy(&output.stderr).trim()))
+    }
+}
+
+/// Separa o cabeçalho do arquivo (diff --git ... +++ b/...) dos hunks.
+/// Cada linha mantém o próprio terminador, inclusive o "\r" de arquivos CRLF.
+fn parse_diff(diff: &[u8]) -> Result<(Vec<&[u8]>, Vec<Hunk<'_>>), String> {
+    if diff.iter().all(u8::is_ascii_whitespace) {
+        return Err("O arquivo não tem alterações para aplicar".into());
+    }
+
+    let mut file_header = Vec::new();
+    let mut hunks: Vec<Hunk> = Vec::new();
+
+    for line in diff.split_inclusive(|b| *b == b'\n') {
+        if line.starts_with(b"@@") {
+            // O cabeçalho é ASCII até o segundo "@@"; o contexto da função depois dele é descartado
+            let text = String::from_utf8_lossy(line);
+            let header = header_of(&text).to_string();
+            let (old, new) = header
+                .trim_matches(|c| c == '@' || c == ' ')
+                .split_once(' ')
+                .ok_or_else(|| format!("Cabeçalho de hunk inválido: {}", text.trim_end()))?;
+            let (old_start, old_count) = parse_range(old.trim_start_matches('-'));
+            let (new_start, new_count) = parse_range(new.trim_start_matches('+'));
+            hunks.push(Hunk { header, old_start, old_count, new_start, new_count, lines: Vec::new() });
+        } else if let Some(hunk) = hunks.last_mut() {
+            hunk.lines.push(line);
+        } else {
+            file_header.push(line);
+        }
+    }
+
+    Ok((file_header, hunks))
+}
+
+/// Reconstrói o corpo do hunk só com as alterações selecionadas. Uma linha não selecionada vira
+/// contexto se existe no lado de partida do patch (o lado "novo" quando aplicado com --reverse) e some caso contrário.
+fn build_hunk(hunk: &Hunk, selection: &HunkSelection, reverse: bool) -> Option<(Vec<u8>, u32, u32)> {
+    let selected = |side: DiffSide, number: u32| match &selection.lines {
+        None => true,
+        Some(ranges) => ranges.iter().any(|r| r.side == side && (r.start..=r.end).contains(&number)),
+    };
+
+    let mut body = Vec::new();
+    let (mut old_line, mut new_line) = (hunk.old_start, hunk.new_start);
+    let (mut old_count, mut new_count) = (0, 0);
+    let mut changed = false;
+    let mut dropped = false;
+
+    for line in &hunk.lines {
+        let (side, number) = match line.first() {
+            Some(b'-') => (Some(DiffSide::Old), old_line),
+            Some(b'+') => (Some(DiffSide::New), new_line),
+            // "\ No newline at end of file" acompanha a linha anterior
+            Some(b'\\') => {
+                if !dropped {
+                    body.extend_from_slice(line);
+                }
+                continue;
+            }
+            _ => (None, 0),
+        };
+
+        dropped = false;
+        match side {
+            Some(side) => {
+                if side == DiffSide::Old {
+                    old_line += 1;
+                } else {
+                    new_line += 1;
+                }
+                if selected(side, number) {
+                    changed = true;
+                    body.extend_from_slice(line);
+                    if side == DiffSide::Old { old_count += 1 } else { new_count += 1 }
+                } else if (side == DiffSide::Old) != reverse {
+                    push_context(&mut body, line);
+                    old_count += 1;
+                    new_count += 1;
+                } else {
+                    dropped = true;
+                }
+            }
+            None => {
+                old_line += 1;
+                new_line += 1;
+                push_context(&mut body, line);
+                old_count += 1;
+                new_count += 1;
+            }
+        }
+    }
+
+    changed.then_some((body, old_count, new_count))
+}
+
+/// Troca o marcador da linha por " " e mantém o restante byte a byte
+fn push_context(body: &mut Vec<u8>, line: &[u8]) {
+    body.push(b' ');
+    body.extend_from_slice(line.get(1..).unwrap_or_default());
+}
+
+/// Compara "<antigo>..<novo>" aceitando abreviações de tamanhos diferentes
+fn same_blobs(current: &str, expected: &str) -> bool {
+    let same = |a: &str, b: &str| !a.is_empty() && !b.is_empty() && (a.starts_with(b) || b.starts_with(a));
+    match (current.split_once(".."), expected.trim().split_once("..")) {
+        (Some((old, new)), Some((expected_old, expected_new))) => same(old, expected_old) && same(new, expected_new),
+        _ => false,
+    }
+}
+
+/// "@@ -1,3 +1,4 @@ fn main()" -> "@@ -1,3 +1,4 @@"
+fn header_of(line: &str) -> &str {
+    match line.get(2..).and_then(|rest| rest.find("@@")) {
+        Some(end) => &line[..end + 4],
+        None => line.trim_end(),
+    }
+}
+
+/// "10,7" -> (10, 7); "10" -> (10, 1)
+fn parse_range(range: &str) -> (u32, u32) {
+    let (start, count) = range.split_once(',').unwrap_or((range, "1"));
+    (start.parse().unwrap_or(0), count.parse().unwrap_or(1))
+}
+
+/// Com contagem 0 o git informa a linha anterior ao trecho
+fn side_position(start: u32, count: u32) -> i64 {
+    start as i64 + if count == 0 { 1 } else { 0 }
+}
+
+fn start_for(position: i64, count: u32) -> i64 {
+    if count == 0 { position - 1 } else { position }
+}
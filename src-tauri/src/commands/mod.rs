@@ -8,4 +8,18 @@ pub mod image;
 pub mod merge;
 pub mod pull_request;
 pub mod git_config;
-pub mod dashboard;
\ No newline at end of file
+pub mod dashboard;
+pub mod blame;
+pub mod archive;
+pub mod search;
+pub mod commit_index;
+pub mod tag;
+pub mod commit_policy;
+pub mod hooks;
+pub mod sequencer;
+pub mod rebase;
+pub mod reflog;
+pub mod bisect;
+pub mod patch;
+pub mod author;
+pub mod hunk;
\ No newline at end of file
@@ -0,0 +1,236 @@
+use std::fs;
+use crate::{
+    commands::sequencer::{git_path, unmerged_files},
+    models::patch::{MailboxStatus, PatchApplyResult, PatchApplyTarget, PatchCheckResult, PatchExportOptions, PatchExportResult},
+    utils::{git_command, run_git},
+};
+
+/// Exporta um commit, um intervalo ("a..b") ou, com `base`, os commits de `target` que não estão na base.
+/// Gera um arquivo por commit em `output_path` ou, com `mbox`, um único arquivo nesse caminho.
+#[tauri::command]
+pub fn export_patches(
+    repo_path: String,
+    target: String,
+    base: Option<String>,
+    output_path: String,
+    options: Option<PatchExportOptions>,
+) -> Result<PatchExportResult, String> {
+    let options = options.unwrap_or_default();
+
+    let range = match base.filter(|b| !b.trim().is_empty()) {
+        Some(base) => format!("{}..{}", base, target),
+        None => target.clone(),
+    };
+    let is_range = range.contains("..");
+
+    let mut args: Vec<String> = vec!["format-patch".into()];
+    if options.cover_letter {
+        args.push("--cover-letter".into());
+    }
+    match options.numbered {
+        Some(true) => args.push("--numbered".into()),
+        Some(false) => args.push("--no-numbered".into()),
+        None => {}
+    }
+    if let Some(start) = options.start_number {
+        args.push(format!("--start-number={}", start));
+    }
+    if let Some(prefix) = options.subject_prefix.filter(|p| !p.trim().is_empty()) {
+        args.push(format!("--subject-prefix={}", prefix));
+    }
+    if let Some(count) = options.reroll_count {
+        args.push(format!("--reroll-count={}", count));
+    }
+    if options.mbox {
+        args.push("--stdout".into());
+    } else {
+        args.push("-o".into());
+        args.push(output_path.clone());
+    }
+    if !is_range {
+        args.push("-1".into());
+    }
+    args.push(range.clone());
+
+    let output = git_command(&repo_path)
+        .args(&args)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).to_string());
+    }
+
+    let commits = if is_range {
+        run_git(&repo_path, &["rev-list", "--count", &range])?.trim().parse().unwrap_or(0)
+    } else {
+        1
+    };
+
+    let files = if options.mbox {
+        fs::write(&output_path, &output.stdout).map_err(|e| e.to_string())?;
+        vec![output_path]
+    } else {
+        // O format-patch imprime o caminho de cada arquivo criado
+        String::from_utf8_lossy(&output.stdout).lines().map(String::from).collect()
+    };
+
+    Ok(PatchExportResult { files, commits })
+}
+
+/// Verifica se o patch aplica sem alterar nada (`git apply --check`)
+#[tauri::command]
+pub fn check_patch(repo_path: String, patch_path: String, target: Option<PatchApplyTarget>) -> Result<PatchCheckResult, String> {
+    let files = run_git(&repo_path, &["apply", "--numstat", "-z", &patch_path])?
+        .split('\0')
+        .filter_map(|record| record.rsplit('\t').next())
+        .filter(|f| !f.is_empty())
+        .map(String::from)
+        .collect();
+
+    let mut cmd = git_command(&repo_path);
+    cmd.args(["apply", "--check", "--verbose"]);
+    if let Some(flag) = target.and_then(target_flag) {
+        cmd.arg(flag);
+    }
+    let output = cmd.arg(&patch_path).output().map_err(|e| e.to_string())?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let mut failed_files: Vec<String> = stderr
+        .lines()
+        .filter_map(|line| {
+            // "error: patch failed: <arquivo>:<linha>" ou "error: <arquivo>: does not exist in index"
+            let rest = line.strip_prefix("error: ")?;
+            match rest.strip_prefix("patch failed: ") {
+                Some(location) => location.rsplit_once(':').map(|(file, _)| file.to_string()),
+                None => rest.split_once(": ").map(|(file, _)| file.to_string()),
+            }
+        })
+        .collect();
+    failed_files.dedup();
+
+    Ok(PatchCheckResult {
+        applies: output.status.success(),
+        files,
+        failed_files,
+        message: format!("{}{}", String::from_utf8_lossy(&output.stdout), stderr),
+    })
+}
+
+/// Aplica o patch no working tree, no índice ou em ambos. Com `three_way`, trechos que não
+/// aplicam viram conflitos no arquivo em vez de falhar tudo.
+#[tauri::command]
+pub fn apply_patch(repo_path: String, patch_path: String, target: PatchApplyTarget, three_way: bool) -> Result<PatchApplyResult, String> {
+    let mut cmd = git_command(&repo_path);
+    cmd.arg("apply");
+    if let Some(flag) = target_flag(target) {
+        cmd.arg(flag);
+    }
+    if three_way {
+        cmd.arg("--3way");
+    }
+    let output = cmd.arg(&patch_path).output().map_err(|e| e.to_string())?;
+
+    let message = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+    let conflicting_files = if three_way { unmerged_files(&repo_path) } else { Vec::new() };
+
+    if !output.status.success() && conflicting_files.is_empty() {
+        return Err(message);
+    }
+
+    Ok(PatchApplyResult {
+        success: output.status.success() && conflicting_files.is_empty(),
+        conflicting_files,
+        message,
+    })
+}
+
+/// Aplica uma série de patches (mbox ou arquivos do format-patch) como commits com `git am`
+#[tauri::command]
+pub fn apply_mailbox(repo_path: String, patch_paths: Vec<String>, three_way: bool) -> Result<MailboxStatus, String> {
+    if patch_paths.is_empty() {
+        return Err("Nenhum patch informado".into());
+    }
+    if mailbox_in_progress(&repo_path)? {
+        return Err("Já existe um git am em andamento".into());
+    }
+
+    let mut args = vec!["am".to_string()];
+    if three_way {
+        args.push("--3way".into());
+    }
+    args.extend(patch_paths);
+    run_am(&repo_path, &args)
+}
+
+#[tauri::command]
+pub fn mailbox_continue(repo_path: String) -> Result<MailboxStatus, String> {
+    run_am(&repo_path, &["am".into(), "--continue".into()])
+}
+
+#[tauri::command]
+pub fn mailbox_skip(repo_path: String) -> Result<MailboxStatus, String> {
+    run_am(&repo_path, &["am".into(), "--skip".into()])
+}
+
+#[tauri::command]
+pub fn mailbox_abort(repo_path: String) -> Result<String, String> {
+    run_git(&repo_path, &["am", "--abort"])
+}
+
+fn target_flag(target: PatchApplyTarget) -> Option<&'static str> {
+    match target {
+        PatchApplyTarget::Worktree => None,
+        PatchApplyTarget::Index => Some("--cached"),
+        PatchApplyTarget::Both => Some("--index"),
+    }
+}
+
+fn mailbox_in_progress(repo_path: &str) -> Result<bool, String> {
+    Ok(git_path(repo_path, "rebase-apply/applying")?.exists())
+}
+
+fn run_am(repo_path: &str, args: &[String]) -> Result<MailboxStatus, String> {
+    let output = git_command(repo_path)
+        .env("GIT_EDITOR", "true")
+        .args(args)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    let message = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+
+    if !mailbox_in_progress(repo_path)? {
+        if !output.status.success() {
+            return Err(message);
+        }
+        return Ok(MailboxStatus {
+            in_progress: false,
+            current: 0,
+            total: 0,
+            failed_subject: None,
+            conflicting_files: Vec::new(),
+            message,
+        });
+    }
+
+    // O git am guarda o progresso em .git/rebase-apply
+    let dir = git_path(repo_path, "rebase-apply")?;
+    let read_number = |name: &str| -> usize {
+        fs::read_to_string(dir.join(name))
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(0)
+    };
+    let failed_subject = fs::read_to_string(dir.join("info"))
+        .ok()
+        .and_then(|info| info.lines().find_map(|l| l.strip_prefix("Subject: ").map(String::from)));
+
+    Ok(MailboxStatus {
+        in_progress: true,
+        current: read_number("next"),
+        total: read_number("last"),
+        failed_subject,
+        conflicting_files: unmerged_files(repo_path),
+        message,
+    })
+}
@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::{
+    commands::sequencer::{git_path, unmerged_files},
+    models::rebase::{RebaseAction, RebaseOptions, RebaseStatus, RebaseStep, RebaseStopReason, RebaseTodoItem},
+    utils::{git_command, run_git},
+};
+
+// Todo e mensagens gerados para o rebase atual
+const PLAN_DIR: &str = "devbrook/rebase";
+
+/// Commits que seriam reaplicados por `git rebase -i <base>`, na ordem do todo.
+/// Com autosquash os commits "fixup!"/"squash!" são reordenados como o git faria.
+#[tauri::command]
+pub fn get_rebase_plan(repo_path: String, base: String, autosquash: bool) -> Result<Vec<RebaseTodoItem>, String> {
+    let range = format!("{}...HEAD", base);
+    let listed = run_git(
+        &repo_path,
+        &["log", "--reverse", "--topo-order", "--right-only", "--cherry-pick", "--no-merges", "--format=%H%x1f%s", &range],
+    )?;
+
+    let items: Vec<RebaseTodoItem> = listed
+        .lines()
+        .filter_map(|line| line.split_once('\x1f'))
+        .map(|(sha, subject)| RebaseTodoItem { action: RebaseAction::Pick, sha: sha.to_string(), subject: subject.to_string() })
+        .collect();
+
+    Ok(if autosquash { autosquash_todo(items) } else { items })
+}
+
+/// Inicia um rebase interativo aplicando o plano informado sem abrir editor.
+/// Plano vazio com autosquash usa o todo que o git gera.
+#[tauri::command]
+pub fn rebase_interactive(
+    repo_path: String,
+    base: String,
+    plan: Vec<RebaseStep>,
+    options: Option<RebaseOptions>,
+) -> Result<RebaseStatus, String> {
+    let options = options.unwrap_or_default();
+    if git_path(&repo_path, "rebase-merge")?.exists() || git_path(&repo_path, "rebase-apply")?.exists() {
+        return Err("Já existe um rebase em andamento".into());
+    }
+
+    clear_plan(&repo_path);
+    let sequence_editor = if plan.is_empty() {
+        if !options.autosquash {
+            return Err("O plano de rebase está vazio".into());
+        }
+        "true".to_string()
+    } else {
+        // O git acrescenta o caminho do todo ao comando: `cp <plano> <todo>`
+        format!("cp {}", shell_quote(&write_plan(&repo_path, &plan)?))
+    };
+
+    let mut args = vec!["rebase", "-i"];
+    args.push(if options.autosquash { "--autosquash" } else { "--no-autosquash" });
+    if options.autostash {
+        args.push("--autostash");
+    }
+    args.push(&base);
+
+    run_rebase(&repo_path, &args, &sequence_editor)
+}
+
+#[tauri::command]
+pub fn rebase_continue(repo_path: String) -> Result<RebaseStatus, String> {
+    run_rebase(&repo_path, &["rebase", "--continue"], "true")
+}
+
+#[tauri::command]
+pub fn rebase_skip(repo_path: String) -> Result<RebaseStatus, String> {
+    run_rebase(&repo_path, &["rebase", "--skip"], "true")
+}
+
+#[tauri::command]
+pub fn rebase_abort(repo_path: String) -> Result<String, String> {
+    let result = run_git(&repo_path, &["rebase", "--abort"]);
+    clear_plan(&repo_path);
+    result
+}
+
+#[tauri::command]
+pub fn get_rebase_status(repo_path: String) -> Result<RebaseStatus, String> {
+    read_status(&repo_path, String::new())
+}
+
+fn run_rebase(repo_path: &str, args: &[&str], sequence_editor: &str) -> Result<RebaseStatus, String> {
+    let output = git_command(repo_path)
+        .env("GIT_SEQUENCE_EDITOR", sequence_editor)
+        .env("GIT_EDITOR", "true")
+        .args(args)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    let message = clean_progress(&format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr)));
+    let status = read_status(repo_path, message.clone())?;
+
+    if !status.in_progress {
+        clear_plan(repo_path);
+        if !output.status.success() {
+            return Err(message);
+        }
+    }
+    Ok(status)
+}
+
+fn read_status(repo_path: &str, message: String) -> Result<RebaseStatus, String> {
+    let dir = git_path(repo_path, "rebase-merge")?;
+    if !dir.exists() {
+        return Ok(RebaseStatus {
+            in_progress: false,
+            done: 0,
+            total: 0,
+            stopped_at: None,
+            stop_reason: None,
+            conflicting_files: Vec::new(),
+            message,
+        });
+    }
+
+    let read_number = |name: &str| -> usize {
+        fs::read_to_string(dir.join(name))
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(0)
+    };
+
+    let stopped_at = run_git(repo_path, &["rev-parse", "--verify", "-q", "REBASE_HEAD"])
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    let conflicting_files = unmerged_files(repo_path);
+    let stop_reason = if !conflicting_files.is_empty() {
+        RebaseStopReason::Conflict
+    } else if dir.join("amend").exists() {
+        // O git grava "amend" ao parar num passo edit
+        RebaseStopReason::Edit
+    } else {
+        RebaseStopReason::Other
+    };
+
+    Ok(RebaseStatus {
+        in_progress: true,
+        done: read_number("msgnum"),
+        total: read_number("end"),
+        stopped_at,
+        stop_reason: Some(stop_reason),
+        conflicting_files,
+        message,
+    })
+}
+
+/// Monta o todo do plano. Mensagens novas são aplicadas com um `exec git commit --amend`
+/// logo depois do passo, assim nenhum editor precisa ser aberto.
+fn write_plan(repo_path: &str, plan: &[RebaseStep]) -> Result<PathBuf, String> {
+    let dir = plan_dir(repo_path)?;
+    let mut todo = String::new();
+
+    for (index, step) in plan.iter().enumerate() {
+        let action = match step.action {
+            RebaseAction::Reword => RebaseAction::Pick,
+            action => action,
+        };
+        todo.push_str(&format!("{} {}\n", action.as_str(), step.sha));
+
+        let message = step.message.as_deref().filter(|m| !m.trim().is_empty());
+        if step.action == RebaseAction::Reword && message.is_none() {
+            return Err(format!("Informe a nova mensagem do commit {} (reword)", step.sha.chars().take(7).collect::<String>()));
+        }
+        if let Some(message) = message.filter(|_| action != RebaseAction::Drop) {
+            let message_path = dir.join(format!("message-{}", index));
+            fs::write(&message_path, message).map_err(|e| e.to_string())?;
+            todo.push_str(&format!("exec git commit --amend --allow-empty --no-verify -q -F {}\n", shell_quote(&message_path)));
+        }
+    }
+
+    let todo_path = dir.join("todo");
+    fs::write(&todo_path, todo).map_err(|e| e.to_string())?;
+    Ok(todo_path)
+}
+
+/// Mesma reordenação do `--autosquash` do git, sem iniciar um rebase: cada "fixup! X"/"squash! X"
+/// vai para logo depois do commit X (ou do último fixup dele). X é procurado pelo assunto exato,
+/// depois como hash e por fim como início de assunto, sempre entre os commits anteriores.
+fn autosquash_todo(mut items: Vec<RebaseTodoItem>) -> Vec<RebaseTodoItem> {
+    let count = items.len();
+    let mut next: Vec<Option<usize>> = vec![None; count];
+    let mut tail: Vec<Option<usize>> = vec![None; count];
+    let mut moved = vec![false; count];
+    let mut by_subject: HashMap<String, usize> = HashMap::new();
+
+    for i in 0..count {
+        let subject = items[i].subject.clone();
+        let action = if subject.starts_with("fixup! ") {
+            Some(RebaseAction::Fixup)
+        } else if subject.starts_with("squash! ") {
+            Some(RebaseAction::Squash)
+        } else {
+            None
+        };
+
+        if let Some(action) = action {
+            // "fixup! fixup! X" também aponta para X
+            let mut target_subject = subject.as_str();
+            while let Some(rest) = target_subject.strip_prefix("fixup! ").or_else(|| target_subject.strip_prefix("squash! ")) {
+                target_subject = rest;
+            }
+
+            let target = by_subject
+                .get(target_subject)
+                .copied()
+                .or_else(|| {
+                    let is_hash = target_subject.len() >= 4 && target_subject.chars().all(|c| c.is_ascii_hexdigit());
+                    (0..i).find(|&t| is_hash && items[t].sha.starts_with(target_subject))
+                })
+                .or_else(|| (0..i).find(|&t| items[t].subject.starts_with(target_subject)));
+
+            if let Some(target) = target {
+                items[i].action = action;
+                moved[i] = true;
+                let after = tail[target].unwrap_or(target);
+                next[i] = next[after];
+                next[after] = Some(i);
+                tail[target] = Some(i);
+            }
+        }
+
+        by_subject.entry(subject).or_insert(i);
+    }
+
+    let mut order = Vec::with_capacity(count);
+    for i in (0..count).filter(|&i| !moved[i]) {
+        let mut current = Some(i);
+        while let Some(index) = current {
+            order.push(index);
+            current = next[index];
+        }
+    }
+
+    let mut slots: Vec<Option<RebaseTodoItem>> = items.into_iter().map(Some).collect();
+    order.into_iter().filter_map(|i| slots[i].take()).collect()
+}
+
+fn plan_dir(repo_path: &str) -> Result<PathBuf, String> {
+    let dir = git_path(repo_path, PLAN_DIR)?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+fn clear_plan(repo_path: &str) {
+    if let Ok(dir) = git_path(repo_path, PLAN_DIR) {
+        let _ = fs::remove_dir_all(dir);
+    }
+}
+
+/// Remove as linhas de progresso ("Rebasing (2/7)\r") que o git reescreve no terminal
+fn clean_progress(output: &str) -> String {
+    output
+        .lines()
+        .map(|line| line.rsplit('\r').next().unwrap_or("").replace("\x1b[K", ""))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn shell_quote(path: &Path) -> String {
+    format!("'{}'", path.to_string_lossy().replace('\'', "'\\''"))
+}
@@ -0,0 +1,148 @@
+use crate::{
+    commands::{sequencer::git_path, stage::reset_to},
+    models::{reflog::{ReflogEntry, UndoResult}, reset::ResetMode},
+    utils::run_git,
+};
+
+/// Lê o reflog de HEAD (padrão) ou de uma branch, do mais recente para o mais antigo
+#[tauri::command]
+pub fn get_reflog(repo_path: String, reference: Option<String>, limit: Option<usize>) -> Result<Vec<ReflogEntry>, String> {
+    let reference = reference.filter(|r| !r.trim().is_empty()).unwrap_or_else(|| "HEAD".into());
+
+    // Com --date=unix o seletor vira "<ref>@{<timestamp>}"
+    let mut args = vec!["log".to_string(), "-g".into(), "-z".into(), "--date=unix".into(), "--format=%H%x1f%gd%x1f%gs".into()];
+    if let Some(limit) = limit {
+        args.push(format!("--max-count={}", limit));
+    }
+    args.push(reference.clone());
+    args.push("--".into());
+
+    let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+    let output = run_git(&repo_path, &args)?;
+
+    Ok(output
+        .split('\0')
+        .filter(|r| !r.trim().is_empty())
+        .enumerate()
+        .filter_map(|(index, record)| {
+            let mut fields = record.trim_start_matches('\n').split('\x1f');
+            let hash = fields.next()?.to_string();
+            let timestamp = fields
+                .next()?
+                .rsplit_once('{')
+                .and_then(|(_, t)| t.trim_end_matches('}').parse().ok())
+                .unwrap_or(0);
+            let subject = fields.next().unwrap_or("");
+            let (action, message) = subject.split_once(": ").unwrap_or((subject, ""));
+
+            Some(ReflogEntry {
+                selector: format!("{}@{{{}}}", reference, index),
+                hash,
+                action: action.to_string(),
+                message: message.to_string(),
+                timestamp,
+            })
+        })
+        .collect())
+}
+
+/// Move HEAD (ou a branch atual) com reset, ou uma outra branch com update-ref, para o commit de uma entrada
+#[tauri::command]
+pub fn restore_reflog_entry(repo_path: String, reference: String, hash: String, mode: Option<ResetMode>) -> Result<String, String> {
+    let current = current_branch(&repo_path);
+    let short = reference.strip_prefix("refs/heads/").unwrap_or(&reference);
+
+    if reference == "HEAD" || current.as_deref() == Some(short) {
+        return reset_to(repo_path, hash, mode.unwrap_or(ResetMode::Keep));
+    }
+
+    let full_ref = format!("refs/heads/{}", short);
+    let reason = format!("reflog: restaurado para {}", hash);
+    run_git(&repo_path, &["update-ref", "-m", &reason, &full_ref, &hash])
+}
+
+/// Desfaz a última transição de HEAD registrada no reflog.
+/// Commit/amend voltam com `reset --soft` (as alterações ficam no stage), rebase volta para o
+/// estado anterior ao início, checkout volta para a origem e as demais operações usam `reset --keep`.
+#[tauri::command]
+pub fn undo_last_operation(repo_path: String, dry_run: Option<bool>) -> Result<UndoResult, String> {
+    let dry_run = dry_run.unwrap_or(false);
+    if git_path(&repo_path, "rebase-merge")?.exists() || git_path(&repo_path, "rebase-apply")?.exists() {
+        return Err("Há um rebase em andamento; conclua ou aborte antes de desfazer".into());
+    }
+
+    let latest = get_reflog(repo_path.clone(), None, Some(1))?
+        .into_iter()
+        .next()
+        .ok_or("O reflog está vazio")?;
+    let branch = current_branch(&repo_path);
+    let reference = branch.clone().unwrap_or_else(|| "HEAD".into());
+    let head = resolve(&repo_path, "HEAD")?;
+    let kind = latest.action.split_whitespace().next().unwrap_or("");
+
+    if kind == "checkout" {
+        // "moving from <origem> to <destino>"
+        let previous = latest
+            .message
+            .strip_prefix("moving from ")
+            .and_then(|m| m.split_once(" to "))
+            .map(|(from, _)| from.to_string())
+            .ok_or("Não foi possível identificar a origem do checkout")?;
+
+        if !dry_run {
+            run_git(&repo_path, &["checkout", &previous])?;
+        }
+        return Ok(UndoResult {
+            action: latest.action,
+            reference: "HEAD".into(),
+            from: reference,
+            to: previous.clone(),
+            applied: !dry_run,
+            message: format!("Checkout de volta para {}", previous),
+        });
+    }
+
+    let (target, mode) = if kind == "rebase" || latest.action.ends_with("(finish)") {
+        // O estado anterior é a entrada logo antes do "(start)" do rebase
+        let entries = get_reflog(repo_path.clone(), None, Some(1000))?;
+        let start = entries
+            .iter()
+            .position(|e| e.action.ends_with("(start)"))
+            .ok_or("Início do rebase não encontrado no reflog")?;
+        let previous = entries.get(start + 1).ok_or("Não há estado anterior ao rebase")?;
+        (previous.hash.clone(), ResetMode::Keep)
+    } else {
+        let mode = match latest.action.as_str() {
+            "commit (initial)" => return Err("O primeiro commit do repositório não pode ser desfeito".into()),
+            "commit" | "commit (amend)" => ResetMode::Soft,
+            _ if matches!(kind, "commit" | "reset" | "merge" | "cherry-pick" | "revert" | "pull" | "am") => ResetMode::Keep,
+            _ => return Err(format!("Não é possível desfazer a operação \"{}\"", latest.action)),
+        };
+        (resolve(&repo_path, "HEAD@{1}")?, mode)
+    };
+
+    if !dry_run {
+        reset_to(repo_path, target.clone(), mode)?;
+    }
+
+    Ok(UndoResult {
+        message: format!("{} volta para {}", reference, &target[..target.len().min(7)]),
+        action: latest.action,
+        reference,
+        from: head,
+        to: target,
+        applied: !dry_run,
+    })
+}
+
+fn current_branch(repo_path: &str) -> Option<String> {
+    run_git(repo_path, &["symbolic-ref", "--short", "-q", "HEAD"])
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn resolve(repo_path: &str, rev: &str) -> Result<String, String> {
+    let rev = format!("{}^{{commit}}", rev);
+    run_git(repo_path, &["rev-parse", "--verify", &rev]).map(|s| s.trim().to_string())
+}
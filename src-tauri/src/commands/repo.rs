@@ -1,7 +1,7 @@
 use std::path::Path;
 use crate::models::pull::GitPullResult;
-use tauri::command;
-use crate::utils::git_command;
+use tauri::{command, AppHandle};
+use crate::{commands::{commit_index::refresh_index_if_present, hooks::run_with_hooks}, models::hook::GitCommandError, utils::git_command};
 use base64::{engine::general_purpose, Engine as _};
 
 #[tauri::command]
@@ -13,14 +13,17 @@ pub fn open_repo(path: String) -> Result<String, String> {
     }
 }
 
-#[tauri::command]
+/// Roda fora da thread principal: o hook pre-push e a rede podem demorar
+#[tauri::command(async)]
 pub fn push_repo(
+    app: AppHandle,
     path: String,
     remote: Option<String>,
     branch: Option<String>,
     token: Option<String>,
     provider: Option<String>,
-) -> Result<String, String> {
+    no_verify: Option<bool>,
+) -> Result<String, GitCommandError> {
 
     let remote_name = remote.unwrap_or("origin".to_string());
     let branch_name = branch.unwrap_or("HEAD".to_string());
@@ -50,23 +53,29 @@ pub fn push_repo(
         }
     }
 
-    let output = cmd
-        .args(["push", "-u", &remote_name, &branch_name])
-        .output()
-        .map_err(|e| e.to_string())?;
+    cmd.args(["push", "-u", &remote_name, &branch_name]);
+    if no_verify.unwrap_or(false) {
+        cmd.arg("--no-verify");
+    }
 
-    if output.status.success() {
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    let output = run_with_hooks(&app, "push", cmd)?;
+
+    if output.success {
+        Ok(output.stdout)
     } else {
-        let err_msg = String::from_utf8_lossy(&output.stderr).to_string();
+        if output.failed_hook.is_some() {
+            return Err(output.into_error());
+        }
+
+        let err_msg = output.stderr;
         
         if err_msg.contains("fatal: could not read Password") || 
            err_msg.contains("Authentication failed") ||
            err_msg.contains("terminal prompts disabled") {
-            return Err("Erro de Autenticação: Seu token expirou ou é inválido para este repositório.".to_string());
+            return Err("Erro de Autenticação: Seu token expirou ou é inválido para este repositório.".to_string().into());
         }
         
-        Err(err_msg)
+        Err(err_msg.into())
     }
 }
 
@@ -82,6 +91,7 @@ pub fn git_pull(repo_path: String, branch: String) -> Result<GitPullResult, Stri
     let stderr = String::from_utf8_lossy(&output.stderr);
 
     if output.status.success() {
+        refresh_index_if_present(&repo_path);
         return Ok(GitPullResult {
             success: true,
             message: stdout.to_string(),
@@ -114,6 +124,7 @@ pub fn fetch_repo(repo_path: String, remote: String) -> Result<String, String> {
         .map_err(|e| e.to_string())?;
 
     if output.status.success() {
+        refresh_index_if_present(&repo_path);
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     } else {
         Err(String::from_utf8_lossy(&output.stderr).to_string())
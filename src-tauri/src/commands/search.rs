@@ -0,0 +1,220 @@
+use tauri::{Manager, AppHandle, Emitter};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+use crate::{models::search::{CodeSearchBatch, CodeSearchMatch, CodeSearchOptions}, utils::git_command};
+
+const BATCH_SIZE: usize = 200;
+const DEFAULT_MAX_RESULTS: usize = 10_000;
+const MAX_SNIPPET_CHARS: usize = 240;
+
+/// Busca em andamento: o processo fica acessível para ser encerrado mesmo sem novas linhas na saída
+struct ActiveSearch {
+    cancelled: Arc<AtomicBool>,
+    child: Arc<Mutex<Child>>,
+}
+
+/// Buscas em andamento, indexadas pelo id enviado pelo frontend
+fn active_searches() -> &'static Mutex<HashMap<String, ActiveSearch>> {
+    static SEARCHES: OnceLock<Mutex<HashMap<String, ActiveSearch>>> = OnceLock::new();
+    SEARCHES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Executa `git grep` em qualquer ref sem fazer checkout.
+/// Os resultados chegam em lotes pelo evento `code-search-results`.
+#[tauri::command]
+pub async fn search_code(
+    app: AppHandle,
+    repo_path: String,
+    revision: String,
+    query: String,
+    search_id: String,
+    options: Option<CodeSearchOptions>,
+) -> Result<String, String> {
+    if query.is_empty() {
+        return Err("Informe um termo de busca".into());
+    }
+
+    let window = app.get_webview_window("main")
+        .ok_or_else(|| "Janela principal não encontrada".to_string())?;
+    let options = options.unwrap_or_default();
+    let max_results = options.max_results.unwrap_or(DEFAULT_MAX_RESULTS);
+
+    let mut cmd = git_command(&repo_path);
+    cmd.args(["grep", "-z", "-n", "--column", "-I", "--no-color"]);
+    cmd.arg(if options.regex { "-E" } else { "-F" });
+    if !options.case_sensitive {
+        cmd.arg("-i");
+    }
+    if options.whole_word {
+        cmd.arg("-w");
+    }
+    cmd.arg("-e").arg(&query).arg(&revision).arg("--");
+
+    for glob in options.include.iter().filter(|g| !g.trim().is_empty()) {
+        cmd.arg(format!(":(glob){}", glob.trim()));
+    }
+    if options.include.iter().all(|g| g.trim().is_empty()) && !options.exclude.is_empty() {
+        // Exclusões precisam de ao menos um pathspec positivo
+        cmd.arg(".");
+    }
+    for glob in options.exclude.iter().filter(|g| !g.trim().is_empty()) {
+        cmd.arg(format!(":(exclude,glob){}", glob.trim()));
+    }
+
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+    let mut child = cmd.spawn().map_err(|e| e.to_string())?;
+    let stdout = child.stdout.take().ok_or("Falha ao capturar a saída do git grep")?;
+    let mut stderr = child.stderr.take().ok_or("Falha ao capturar a saída do git grep")?;
+
+    // O stderr é lido à parte: se o pipe enchesse, o git travaria antes de fechar o stdout
+    let stderr_reader = thread::spawn(move || {
+        let mut err_output = String::new();
+        let _ = stderr.read_to_string(&mut err_output);
+        err_output
+    });
+
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    let child = Arc::new(Mutex::new(child));
+    active_searches()
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(search_id.clone(), ActiveSearch { cancelled: cancel_flag.clone(), child: child.clone() });
+
+    let emit_id = search_id.clone();
+    thread::spawn(move || {
+        let emit = |matches: Vec<CodeSearchMatch>, done: bool, cancelled: bool, truncated: bool, error: Option<String>| {
+            let _ = window.emit("code-search-results", CodeSearchBatch {
+                search_id: emit_id.clone(),
+                matches,
+                done,
+                cancelled,
+                truncated,
+                error,
+            });
+        };
+
+        // Na árvore de uma ref o git prefixa cada caminho com "<ref>:"
+        let rev_prefix = format!("{}:", revision);
+        let mut reader = BufReader::new(stdout);
+        let mut record = Vec::new();
+        let mut batch = Vec::with_capacity(BATCH_SIZE);
+        let mut total = 0;
+        let mut truncated = false;
+
+        while reader.read_until(b'\n', &mut record).unwrap_or(0) > 0 {
+            if cancel_flag.load(Ordering::Relaxed) {
+                break;
+            }
+
+            if let Some(found) = parse_grep_record(&record, &rev_prefix) {
+                batch.push(found);
+                total += 1;
+            }
+            record.clear();
+
+            if total >= max_results {
+                truncated = true;
+                break;
+            }
+            if batch.len() >= BATCH_SIZE {
+                emit(std::mem::take(&mut batch), false, false, false, None);
+            }
+        }
+
+        let cancelled = cancel_flag.load(Ordering::Relaxed);
+        if cancelled || truncated {
+            if let Ok(mut child) = child.lock() {
+                let _ = child.kill();
+            }
+        }
+        let status = wait_child(&child);
+        let err_output = stderr_reader.join().unwrap_or_default();
+
+        // git grep retorna 1 quando não encontra nada, o que não é erro
+        let error = match status {
+            Ok(s) if s.success() || s.code() == Some(1) || cancelled || truncated => None,
+            Ok(_) => Some(err_output.trim().to_string()),
+            Err(e) => Some(e.to_string()),
+        };
+
+        if let Ok(mut searches) = active_searches().lock() {
+            searches.remove(&emit_id);
+        }
+        emit(batch, true, cancelled, truncated, error);
+    });
+
+    Ok(search_id)
+}
+
+#[tauri::command]
+pub fn cancel_code_search(search_id: String) -> Result<(), String> {
+    let child = {
+        let searches = active_searches().lock().map_err(|e| e.to_string())?;
+        match searches.get(&search_id) {
+            Some(search) => {
+                search.cancelled.store(true, Ordering::Relaxed);
+                search.child.clone()
+            }
+            None => return Ok(()),
+        }
+    };
+
+    // Encerrar o processo fecha o stdout e destrava a leitura na thread da busca
+    if let Ok(mut child) = child.lock() {
+        let _ = child.kill();
+    }
+    Ok(())
+}
+
+/// Espera o processo terminar sem segurar o lock, para que o cancelamento consiga encerrá-lo
+fn wait_child(child: &Mutex<Child>) -> std::io::Result<ExitStatus> {
+    loop {
+        let status = child
+            .lock()
+            .map_err(|e| std::io::Error::other(e.to_string()))?
+            .try_wait()?;
+        if let Some(status) = status {
+            return Ok(status);
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
+/// Formato com -z: "<ref>:<caminho>\0<linha>\0<coluna>\0<conteúdo>\n"
+fn parse_grep_record(record: &[u8], rev_prefix: &str) -> Option<CodeSearchMatch> {
+    let text = String::from_utf8_lossy(record);
+    let mut parts = text.trim_end_matches(['\n', '\r']).splitn(4, '\0');
+
+    let raw_path = parts.next()?;
+    let line_number = parts.next()?.parse::<usize>().ok()?;
+    let column = parts.next()?.parse::<usize>().ok()?;
+    let content = parts.next().unwrap_or("");
+
+    let file_path = raw_path.strip_prefix(rev_prefix).unwrap_or(raw_path).to_string();
+
+    Some(CodeSearchMatch {
+        file_path,
+        line_number,
+        column,
+        snippet: snippet_around(content, column),
+    })
+}
+
+/// Recorta linhas muito longas (ex: arquivos minificados) ao redor da coluna encontrada
+fn snippet_around(content: &str, column: usize) -> String {
+    if content.chars().count() <= MAX_SNIPPET_CHARS {
+        return content.to_string();
+    }
+
+    // A coluna do git é em bytes e começa em 1
+    let byte_col = column.saturating_sub(1).min(content.len());
+    let char_col = content.char_indices().take_while(|(i, _)| *i < byte_col).count();
+    let start = char_col.saturating_sub(MAX_SNIPPET_CHARS / 4);
+
+    content.chars().skip(start).take(MAX_SNIPPET_CHARS).collect()
+}
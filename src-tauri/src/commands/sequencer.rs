@@ -0,0 +1,471 @@
+use std::env::temp_dir;
+use std::fs;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use crate::{models::sequencer::{CherryPickOptions, RevertOptions, SequencerConflict, SequencerResult}, utils::{git_command, run_git}};
+
+const COMBINED_REVERT_STATE: &str = "devbrook/revert-combined.json";
+const CHERRY_PICK_ONTO_STATE: &str = "devbrook/cherry-pick-onto.json";
+
+/// Aplica um ou mais commits (ou intervalos "a..b") na branch atual, na ordem informada.
+#[tauri::command]
+pub fn cherry_pick(repo_path: String, commits: Vec<String>, options: Option<CherryPickOptions>) -> Result<SequencerResult, String> {
+    let options = options.unwrap_or_default();
+    let shas = expand_commits(&repo_path, &commits)?;
+    run_sequencer(&repo_path, "cherry-pick", &cherry_pick_args(&options), &shas)
+}
+
+/// Aplica os commits em outra branch sem mexer no checkout atual, usando um worktree temporário.
+/// Em caso de conflito o worktree é mantido (o caminho vem em `worktree`) para ser resolvido e
+/// concluído com `cherry_pick_onto_continue`, ou descartado com `cherry_pick_onto_abort`.
+#[tauri::command]
+pub fn cherry_pick_onto(
+    repo_path: String,
+    target_branch: String,
+    commits: Vec<String>,
+    options: Option<CherryPickOptions>,
+) -> Result<SequencerResult, String> {
+    let options = options.unwrap_or_default();
+    if options.no_commit {
+        return Err("O modo sem commit não é suportado ao aplicar em outra branch".into());
+    }
+    if let Some(pending) = load_onto_state(&repo_path)? {
+        return Err(format!(
+            "Já existe um cherry-pick em andamento em {} ({}); continue ou cancele antes de iniciar outro",
+            pending.target_branch, pending.worktree
+        ));
+    }
+    if let Some(checked_out) = worktree_with_branch(&repo_path, &target_branch)? {
+        return Err(format!(
+            "A branch {} já está em uso no worktree {}; faça o cherry-pick por lá",
+            target_branch, checked_out
+        ));
+    }
+
+    let shas = expand_commits(&repo_path, &commits)?;
+    let worktree = temp_worktree_path("cherry-pick");
+    let worktree_str = worktree.to_string_lossy().to_string();
+
+    run_git(&repo_path, &["worktree", "add", &worktree_str, &target_branch])?;
+
+    let result = run_sequencer(&worktree_str, "cherry-pick", &cherry_pick_args(&options), &shas);
+    let state = OntoState { worktree: worktree_str, target_branch };
+    finish_onto(&repo_path, state, result)
+}
+
+/// Conclui um `cherry_pick_onto` parado por conflito, depois de resolvido no worktree
+#[tauri::command]
+pub fn cherry_pick_onto_continue(repo_path: String) -> Result<SequencerResult, String> {
+    let state = load_onto_state(&repo_path)?.ok_or("Nenhum cherry-pick em outra branch em andamento")?;
+    let result = continue_sequencer(&state.worktree, "cherry-pick");
+    finish_onto(&repo_path, state, result)
+}
+
+/// Cancela um `cherry_pick_onto` parado: a branch volta ao estado anterior e o worktree é removido
+#[tauri::command]
+pub fn cherry_pick_onto_abort(repo_path: String) -> Result<String, String> {
+    let state = load_onto_state(&repo_path)?.ok_or("Nenhum cherry-pick em outra branch em andamento")?;
+    let _ = run_git(&state.worktree, &["cherry-pick", "--abort"]);
+    remove_onto_worktree(&repo_path, &state)?;
+    Ok(format!("Cherry-pick em {} cancelado", state.target_branch))
+}
+
+/// Cherry-pick em outra branch interrompido, guardado no repositório principal
+#[derive(Serialize, Deserialize)]
+struct OntoState {
+    worktree: String,
+    target_branch: String,
+}
+
+/// Conflitos mantêm o worktree e o estado do sequencer; qualquer outro desfecho remove o worktree
+fn finish_onto(repo_path: &str, state: OntoState, result: Result<SequencerResult, String>) -> Result<SequencerResult, String> {
+    let conflicted = matches!(&result, Ok(r) if r.in_progress && r.conflict.as_ref().is_some_and(|c| !c.files.is_empty()));
+    if conflicted {
+        save_onto_state(repo_path, &state)?;
+        return result.map(|mut r| {
+            r.message = format!("Conflito ao aplicar em {}; resolva os arquivos em {} e continue", state.target_branch, state.worktree);
+            r.worktree = Some(state.worktree);
+            r
+        });
+    }
+
+    // Parada sem conflito (commit que ficaria vazio): não há o que resolver, então nada é aplicado
+    let stopped = matches!(&result, Ok(r) if r.in_progress);
+    if stopped {
+        let _ = run_git(&state.worktree, &["cherry-pick", "--abort"]);
+    }
+    remove_onto_worktree(repo_path, &state)?;
+
+    result.map(|mut r| {
+        if stopped {
+            r.in_progress = false;
+            r.message = format!("Um dos commits ficaria vazio em {} (já aplicado?); nenhuma alteração foi feita", state.target_branch);
+        }
+        r
+    })
+}
+
+fn remove_onto_worktree(repo_path: &str, state: &OntoState) -> Result<(), String> {
+    let _ = run_git(repo_path, &["worktree", "remove", "--force", &state.worktree]);
+    let _ = fs::remove_dir_all(&state.worktree);
+    let _ = run_git(repo_path, &["worktree", "prune"]);
+
+    let path = git_path(repo_path, CHERRY_PICK_ONTO_STATE)?;
+    if path.exists() {
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn load_onto_state(repo_path: &str) -> Result<Option<OntoState>, String> {
+    match fs::read_to_string(git_path(repo_path, CHERRY_PICK_ONTO_STATE)?) {
+        Ok(content) => serde_json::from_str(&content).map(Some).map_err(|e| e.to_string()),
+        Err(_) => Ok(None),
+    }
+}
+
+fn save_onto_state(repo_path: &str, state: &OntoState) -> Result<(), String> {
+    let path = git_path(repo_path, CHERRY_PICK_ONTO_STATE)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string(state).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())
+}
+
+/// Worktree (o principal ou outro qualquer) que está com a branch em checkout
+fn worktree_with_branch(repo_path: &str, branch: &str) -> Result<Option<String>, String> {
+    let full_ref = if branch.starts_with("refs/") { branch.to_string() } else { format!("refs/heads/{}", branch) };
+    let listed = run_git(repo_path, &["worktree", "list", "--porcelain"])?;
+
+    // Blocos "worktree <caminho>" seguidos de "branch refs/heads/<nome>" (ou "detached")
+    let mut current = None;
+    for line in listed.lines() {
+        if let Some(path) = line.strip_prefix("worktree ") {
+            current = Some(path.to_string());
+        } else if line.strip_prefix("branch ") == Some(full_ref.as_str()) {
+            return Ok(current);
+        }
+    }
+    Ok(None)
+}
+
+#[tauri::command]
+pub fn cherry_pick_continue(repo_path: String) -> Result<SequencerResult, String> {
+    continue_sequencer(&repo_path, "cherry-pick")
+}
+
+#[tauri::command]
+pub fn cherry_pick_abort(repo_path: String) -> Result<String, String> {
+    run_git(&repo_path, &["cherry-pick", "--abort"])
+}
+
+fn cherry_pick_args(options: &CherryPickOptions) -> Vec<String> {
+    let mut args = Vec::new();
+    if options.record_origin {
+        args.push("-x".into());
+    }
+    if let Some(parent) = options.mainline {
+        args.push("-m".into());
+        args.push(parent.to_string());
+    }
+    if options.no_commit {
+        args.push("--no-commit".into());
+    }
+    args
+}
+
+/// Reverte um ou mais commits (ou intervalos), do mais novo para o mais antigo.
+/// Com `single_commit` todas as reversões são gravadas em um único commit.
+#[tauri::command]
+pub fn revert(repo_path: String, commits: Vec<String>, options: Option<RevertOptions>) -> Result<SequencerResult, String> {
+    let options = options.unwrap_or_default();
+    let mut shas = expand_commits(&repo_path, &commits)?;
+    shas.reverse();
+
+    if !options.single_commit {
+        let mut args = Vec::new();
+        if let Some(parent) = options.mainline {
+            args.push("-m".to_string());
+            args.push(parent.to_string());
+        }
+        return run_sequencer(&repo_path, "revert", &args, &shas);
+    }
+
+    if run_git(&repo_path, &["diff", "--cached", "--quiet"]).is_err() {
+        return Err("Há alterações no stage; faça commit ou stash antes de reverter em um único commit".into());
+    }
+
+    let message = match options.message.filter(|m| !m.trim().is_empty()) {
+        Some(message) => message,
+        None => combined_revert_message(&repo_path, &shas)?,
+    };
+    let state = CombinedRevert { message, mainline: options.mainline, done: Vec::new(), pending: shas };
+    apply_combined_revert(&repo_path, state)
+}
+
+#[tauri::command]
+pub fn revert_continue(repo_path: String) -> Result<SequencerResult, String> {
+    let mut state = match load_combined_revert(&repo_path)? {
+        Some(state) => state,
+        None => return continue_sequencer(&repo_path, "revert"),
+    };
+
+    let files = unmerged_files(&repo_path);
+    if !files.is_empty() {
+        return Ok(combined_conflict(state, files, "Ainda há arquivos em conflito".into()));
+    }
+
+    run_git(&repo_path, &["revert", "--quit"])?;
+    let current = state.pending.remove(0);
+    state.done.push(current);
+    apply_combined_revert(&repo_path, state)
+}
+
+#[tauri::command]
+pub fn revert_abort(repo_path: String) -> Result<String, String> {
+    if load_combined_revert(&repo_path)?.is_none() {
+        return run_git(&repo_path, &["revert", "--abort"]);
+    }
+
+    // As reversões já aplicadas estão apenas no índice; voltar ao HEAD descarta todas
+    run_git(&repo_path, &["revert", "--quit"])?;
+    run_git(&repo_path, &["reset", "--merge", "HEAD"])?;
+    clear_combined_revert(&repo_path)?;
+    Ok("Revert cancelado".into())
+}
+
+/// Estado de um revert combinado interrompido por conflito.
+/// O `git revert --continue` sempre cria um commit por reversão, então a sequência é controlada aqui.
+#[derive(Serialize, Deserialize)]
+struct CombinedRevert {
+    message: String,
+    mainline: Option<u32>,
+    done: Vec<String>,
+    pending: Vec<String>, // O primeiro é o commit em andamento
+}
+
+fn apply_combined_revert(repo_path: &str, mut state: CombinedRevert) -> Result<SequencerResult, String> {
+    while let Some(sha) = state.pending.first().cloned() {
+        let mut cmd = git_command(repo_path);
+        cmd.args(["revert", "--no-commit"]);
+        if let Some(parent) = state.mainline {
+            cmd.arg("-m").arg(parent.to_string());
+        }
+        let output = cmd.arg(&sha).output().map_err(|e| e.to_string())?;
+
+        if !output.status.success() {
+            let message = String::from_utf8_lossy(&output.stderr).to_string();
+            let files = unmerged_files(repo_path);
+            if files.is_empty() {
+                // Falha sem conflito: desfaz o que já foi aplicado no índice
+                let _ = run_git(repo_path, &["revert", "--quit"]);
+                let _ = run_git(repo_path, &["reset", "--merge", "HEAD"]);
+                clear_combined_revert(repo_path)?;
+                return Err(message);
+            }
+            save_combined_revert(repo_path, &state)?;
+            return Ok(combined_conflict(state, files, message));
+        }
+
+        state.pending.remove(0);
+        state.done.push(sha);
+    }
+
+    clear_combined_revert(repo_path)?;
+    let output = git_command(repo_path)
+        .args(["commit", "-m", &state.message])
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    let message = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+    if !output.status.success() {
+        return Err(message);
+    }
+
+    Ok(SequencerResult {
+        success: true,
+        completed: state.done,
+        remaining: Vec::new(),
+        conflict: None,
+        in_progress: false,
+        message,
+        worktree: None,
+    })
+}
+
+fn combined_conflict(mut state: CombinedRevert, files: Vec<String>, message: String) -> SequencerResult {
+    let commit = state.pending.remove(0);
+    SequencerResult {
+        success: false,
+        completed: state.done,
+        remaining: state.pending,
+        conflict: Some(SequencerConflict { commit, files }),
+        in_progress: true,
+        message,
+        worktree: None,
+    }
+}
+
+/// Mensagem no mesmo formato do git, citando todos os commits revertidos
+fn combined_revert_message(repo_path: &str, shas: &[String]) -> Result<String, String> {
+    let mut args = vec!["log", "--no-walk=unsorted", "--format=%H %s"];
+    args.extend(shas.iter().map(|s| s.as_str()));
+    let listed = run_git(repo_path, &args)?;
+    let lines: Vec<&str> = listed.lines().collect();
+
+    if let [single] = lines.as_slice() {
+        let (sha, subject) = single.split_once(' ').unwrap_or((single, ""));
+        return Ok(format!("Revert \"{}\"\n\nThis reverts commit {}.", subject, sha));
+    }
+    Ok(format!("Revert {} commits\n\nThis reverts commits:\n{}", lines.len(), lines.join("\n")))
+}
+
+fn load_combined_revert(repo_path: &str) -> Result<Option<CombinedRevert>, String> {
+    match fs::read_to_string(git_path(repo_path, COMBINED_REVERT_STATE)?) {
+        Ok(content) => serde_json::from_str(&content).map(Some).map_err(|e| e.to_string()),
+        Err(_) => Ok(None),
+    }
+}
+
+fn save_combined_revert(repo_path: &str, state: &CombinedRevert) -> Result<(), String> {
+    let path = git_path(repo_path, COMBINED_REVERT_STATE)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string(state).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())
+}
+
+fn clear_combined_revert(repo_path: &str) -> Result<(), String> {
+    let path = git_path(repo_path, COMBINED_REVERT_STATE)?;
+    if path.exists() {
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Resolve intervalos ("a..b") e refs em hashes completos, do mais antigo para o mais novo
+pub(crate) fn expand_commits(repo_path: &str, commits: &[String]) -> Result<Vec<String>, String> {
+    let mut shas = Vec::new();
+
+    for item in commits.iter().map(|c| c.trim()).filter(|c| !c.is_empty()) {
+        if item.contains("..") {
+            let listed = run_git(repo_path, &["rev-list", "--reverse", item])?;
+            shas.extend(listed.lines().map(String::from));
+        } else {
+            let rev = format!("{}^{{commit}}", item);
+            shas.push(run_git(repo_path, &["rev-parse", "--verify", &rev])?.trim().to_string());
+        }
+    }
+
+    if shas.is_empty() {
+        return Err("Nenhum commit informado".into());
+    }
+    Ok(shas)
+}
+
+/// Executa `git cherry-pick`/`git revert` e descreve onde a sequência parou
+pub(crate) fn run_sequencer(repo_path: &str, operation: &str, options: &[String], shas: &[String]) -> Result<SequencerResult, String> {
+    let output = git_command(repo_path)
+        .env("GIT_EDITOR", "true")
+        .arg(operation)
+        .args(options)
+        .args(shas)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    let message = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+    describe_state(repo_path, operation, shas, output.status.success(), message)
+}
+
+pub(crate) fn continue_sequencer(repo_path: &str, operation: &str) -> Result<SequencerResult, String> {
+    let output = git_command(repo_path)
+        .env("GIT_EDITOR", "true")
+        .args([operation, "--continue"])
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    let message = format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr));
+    describe_state(repo_path, operation, &[], output.status.success(), message)
+}
+
+fn describe_state(repo_path: &str, operation: &str, shas: &[String], success: bool, message: String) -> Result<SequencerResult, String> {
+    let head_ref = if operation == "revert" { "REVERT_HEAD" } else { "CHERRY_PICK_HEAD" };
+    let stopped_at = run_git(repo_path, &["rev-parse", "--verify", "-q", head_ref])
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    if !success && stopped_at.is_none() {
+        return Err(message);
+    }
+
+    // O primeiro item do todo é o próprio commit em que a sequência parou
+    let remaining: Vec<String> = sequencer_todo(repo_path)
+        .into_iter()
+        .filter(|sha| Some(sha) != stopped_at.as_ref())
+        .collect();
+    let completed = shas
+        .iter()
+        .take_while(|sha| Some(*sha) != stopped_at.as_ref() && !remaining.contains(sha))
+        .cloned()
+        .collect();
+
+    let conflict = stopped_at.map(|commit| SequencerConflict { commit, files: unmerged_files(repo_path) });
+
+    Ok(SequencerResult {
+        success: conflict.is_none(),
+        completed,
+        remaining,
+        in_progress: conflict.is_some(),
+        conflict,
+        message,
+        worktree: None,
+    })
+}
+
+pub(crate) fn unmerged_files(repo_path: &str) -> Vec<String> {
+    let unmerged = run_git(repo_path, &["diff", "--name-only", "-z", "--diff-filter=U"]).unwrap_or_default();
+    unmerged.split('\0').filter(|s| !s.is_empty()).map(String::from).collect()
+}
+
+/// Commits ainda pendentes em `.git/sequencer/todo` ("pick <sha> <assunto>")
+fn sequencer_todo(repo_path: &str) -> Vec<String> {
+    let todo_path = match git_path(repo_path, "sequencer/todo") {
+        Ok(p) => p,
+        Err(_) => return Vec::new(),
+    };
+
+    let todo = fs::read_to_string(todo_path).unwrap_or_default();
+    let abbreviated: Vec<&str> = todo
+        .lines()
+        .filter(|l| !l.starts_with('#'))
+        .filter_map(|l| l.split_whitespace().nth(1))
+        .collect();
+
+    if abbreviated.is_empty() {
+        return Vec::new();
+    }
+
+    // O todo guarda hashes abreviados
+    let mut args = vec!["rev-parse"];
+    args.extend(abbreviated);
+    run_git(repo_path, &args)
+        .map(|out| out.lines().map(String::from).collect())
+        .unwrap_or_default()
+}
+
+/// Caminho absoluto de um arquivo dentro do diretório do git (respeita worktrees)
+pub(crate) fn git_path(repo_path: &str, name: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(run_git(repo_path, &["rev-parse", "--git-path", name])?.trim());
+    Ok(if path.is_absolute() { path } else { PathBuf::from(repo_path).join(path) })
+}
+
+pub(crate) fn temp_worktree_path(operation: &str) -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    temp_dir().join(format!("devbrook-{}-{}", operation, nanos))
+}
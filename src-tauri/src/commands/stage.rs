@@ -3,7 +3,7 @@ use tauri::command;
 use std::fs;
 use std::env::temp_dir;
 use serde_json::json;
-use crate::utils::git_command;
+use crate::{models::{diff::DiffOptions, reset::{ResetCommit, ResetMode, ResetPreview}}, utils::{git_command, run_git}};
 
 #[tauri::command]
 pub fn list_local_changes(path: String) -> Result<Vec<serde_json::Value>, String> {
@@ -169,7 +169,7 @@ pub fn discard_changes(path: String, files: Vec<String>) -> Result<String, Strin
 }
 
 #[tauri::command]
-pub fn get_diff(repo_path: String, file: String, staged: bool) -> Result<serde_json::Value, String> {
+pub fn get_diff(repo_path: String, file: String, staged: bool, options: Option<DiffOptions>) -> Result<serde_json::Value, String> {
 
     let file_path = Path::new(&repo_path).join(&file);
 
@@ -214,10 +214,11 @@ pub fn get_diff(repo_path: String, file: String, staged: bool) -> Result<serde_j
     // 3️⃣ Caso normal → usa git diff
     let mut cmd = git_command(&repo_path);
 
+    cmd.arg("diff").args(options.unwrap_or_default().to_args());
     if staged {
-        cmd.args(["diff", "--cached", "--"]).arg(&file);
+        cmd.args(["--cached", "--"]).arg(&file);
     } else {
-        cmd.args(["diff", "--"]).arg(&file);
+        cmd.arg("--").arg(&file);
     }
 
     let output = cmd.output().map_err(|e| e.to_string())?;
@@ -253,18 +254,6 @@ pub fn get_diff(repo_path: String, file: String, staged: bool) -> Result<serde_j
     }))
 }
 
-fn run_git(repo_path: &str, args: &[&str]) -> Result<String, String> {
-    let output = git_command(&repo_path)
-        .args(args)
-        .output()
-        .map_err(|e| e.to_string())?;
-
-    if output.status.success() {
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
-    } else {
-        Err(String::from_utf8_lossy(&output.stderr).to_string())
-    }
-}
 #[command]
 pub fn stash_changes(repo_path: String) -> Result<String, String> {
     run_git(&repo_path, &["stash", "push", "-u"])
@@ -279,3 +268,73 @@ pub fn stash_pop(repo_path: String) -> Result<String, String> {
 pub fn reset_hard(repo_path: String) -> Result<String, String> {
     run_git(&repo_path, &["reset", "--hard"])
 }
+
+/// Mostra o que um reset para `target` (commit, branch ou entrada do reflog) faria antes de executá-lo
+#[command]
+pub fn preview_reset(repo_path: String, target: String, mode: ResetMode) -> Result<ResetPreview, String> {
+    let target = resolve_commit(&repo_path, &target)?;
+
+    let range = format!("^{}", target);
+    let log = run_git(&repo_path, &["log", "-z", "--format=%H%x1f%s%x1f%an%x1f%ad", "HEAD", &range])?;
+    let leaving_commits = log
+        .split('\0')
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').split('\x1f');
+            Some(ResetCommit {
+                hash: fields.next().filter(|h| !h.is_empty())?.to_string(),
+                subject: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+            })
+        })
+        .collect();
+
+    // Arquivos versionados com alterações (stage ou working tree)
+    let dirty = name_list(&repo_path, &["diff", "--name-only", "-z", "HEAD"])?;
+
+    let mut preview = ResetPreview {
+        target: target.clone(),
+        mode,
+        leaving_commits,
+        lost_files: Vec::new(),
+        untracked_overwritten: Vec::new(),
+        blocking_files: Vec::new(),
+    };
+
+    match mode {
+        ResetMode::Soft | ResetMode::Mixed => {}
+        ResetMode::Hard => {
+            let untracked = name_list(&repo_path, &["ls-files", "--others", "--exclude-standard", "-z"])?;
+            let in_target = name_list(&repo_path, &["ls-tree", "-r", "-z", "--name-only", &target])?;
+            preview.untracked_overwritten = untracked.into_iter().filter(|f| in_target.contains(f)).collect();
+            preview.lost_files = dirty;
+        }
+        ResetMode::Keep => {
+            let changed = name_list(&repo_path, &["diff", "--name-only", "-z", "HEAD", &target])?;
+            preview.blocking_files = dirty.into_iter().filter(|f| changed.contains(f)).collect();
+        }
+    }
+
+    Ok(preview)
+}
+
+#[command]
+pub fn reset_to(repo_path: String, target: String, mode: ResetMode) -> Result<String, String> {
+    let target = resolve_commit(&repo_path, &target)?;
+    run_git(&repo_path, &["reset", mode.flag(), &target])
+}
+
+fn resolve_commit(repo_path: &str, target: &str) -> Result<String, String> {
+    let rev = format!("{}^{{commit}}", target);
+    run_git(repo_path, &["rev-parse", "--verify", &rev])
+        .map(|s| s.trim().to_string())
+        .map_err(|_| format!("Commit não encontrado: {}", target))
+}
+
+fn name_list(repo_path: &str, args: &[&str]) -> Result<Vec<String>, String> {
+    Ok(run_git(repo_path, args)?
+        .split('\0')
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect())
+}
@@ -0,0 +1,45 @@
+use crate::{commands::commit::signing_config_args, models::signature::SigningOptions, utils::git_command};
+
+/// Cria uma tag em `target`. Com mensagem ou assinatura a tag é anotada.
+#[tauri::command]
+pub fn create_tag(
+    repo_path: String,
+    name: String,
+    target: String,
+    message: Option<String>,
+    signing: Option<SigningOptions>,
+) -> Result<String, String> {
+    let message = message.filter(|m| !m.trim().is_empty());
+    let sign = signing.as_ref().is_some_and(|s| s.sign);
+
+    let mut args = signing_config_args(signing.as_ref());
+    args.push("tag".into());
+
+    if sign {
+        args.push("-s".into());
+    } else if message.is_some() {
+        args.push("-a".into());
+    }
+
+    // Tags assinadas precisam de mensagem; usamos o nome quando não houver
+    if sign || message.is_some() {
+        args.push("-m".into());
+        args.push(message.unwrap_or_else(|| name.clone()));
+    }
+
+    args.push(name.clone());
+    if !target.trim().is_empty() {
+        args.push(target);
+    }
+
+    let output = git_command(&repo_path)
+        .args(&args)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(name)
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).to_string())
+    }
+}
@@ -0,0 +1,118 @@
+use crate::models::graph::{EdgeKind, GraphEdge, GraphLane, GraphRow, GraphState};
+
+/// Calcula coluna, cor e arestas de cada commit a partir dos hashes dos pais.
+/// Os commits precisam vir com filhos antes dos pais (`--date-order` ou `--topo-order`).
+/// `state` é atualizado para permitir continuar o layout na próxima página.
+pub fn layout_rows<'a, I>(commits: I, state: &mut GraphState) -> Vec<GraphRow>
+where
+    I: IntoIterator<Item = (&'a str, Vec<&'a str>)>,
+{
+    let mut rows = Vec::new();
+
+    for (hash, parents) in commits {
+        let lanes_before = state.lanes.clone();
+
+        // 1. Coluna do commit: a primeira que já o esperava, ou a primeira livre
+        let column = match lanes_before.iter().position(|l| l.as_ref().is_some_and(|l| l.hash == hash)) {
+            Some(index) => index,
+            None => {
+                let color = take_color(state);
+                place_lane(&mut state.lanes, GraphLane { hash: hash.to_string(), color })
+            }
+        };
+        let color = state.lanes[column].as_ref().map(|l| l.color).unwrap_or(0);
+
+        let mut edges = Vec::new();
+
+        // 2. Outras colunas que esperavam este commit convergem nele e são liberadas
+        for (index, lane) in lanes_before.iter().enumerate() {
+            match lane {
+                Some(l) if l.hash == hash => {
+                    edges.push(GraphEdge { from: index, to: column, color: l.color, kind: EdgeKind::In });
+                    if index != column {
+                        state.lanes[index] = None;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // 3. O primeiro pai herda a coluna (e a cor); sem pais a coluna termina aqui
+        let mut parents = parents.into_iter();
+        match parents.next() {
+            Some(first) => {
+                if let Some(existing) = lane_expecting(&state.lanes, first, column) {
+                    // O pai já tem coluna: esta linha se junta a ela
+                    let target_color = state.lanes[existing].as_ref().map(|l| l.color).unwrap_or(color);
+                    edges.push(GraphEdge { from: column, to: existing, color: target_color, kind: EdgeKind::Out });
+                    state.lanes[column] = None;
+                } else {
+                    state.lanes[column] = Some(GraphLane { hash: first.to_string(), color });
+                    edges.push(GraphEdge { from: column, to: column, color, kind: EdgeKind::Out });
+                }
+            }
+            None => state.lanes[column] = None,
+        }
+
+        // 4. Demais pais (merge): reaproveita a coluna que já espera o pai ou abre uma nova
+        for parent in parents {
+            let (target, parent_color) = match lane_expecting(&state.lanes, parent, usize::MAX) {
+                Some(index) => (index, state.lanes[index].as_ref().map(|l| l.color).unwrap_or(0)),
+                None => {
+                    let parent_color = take_color(state);
+                    let index = place_lane(&mut state.lanes, GraphLane { hash: parent.to_string(), color: parent_color });
+                    (index, parent_color)
+                }
+            };
+            edges.push(GraphEdge { from: column, to: target, color: parent_color, kind: EdgeKind::Out });
+        }
+
+        // 5. Colunas que não participaram atravessam a linha
+        for (index, lane) in lanes_before.iter().enumerate() {
+            if let Some(l) = lane {
+                if l.hash != hash {
+                    edges.push(GraphEdge { from: index, to: index, color: l.color, kind: EdgeKind::Pass });
+                }
+            }
+        }
+
+        while matches!(state.lanes.last(), Some(None)) {
+            state.lanes.pop();
+        }
+
+        rows.push(GraphRow {
+            column,
+            color,
+            width: lanes_before.len().max(state.lanes.len()).max(column + 1),
+            edges,
+        });
+    }
+
+    rows
+}
+
+fn take_color(state: &mut GraphState) -> usize {
+    let color = state.next_color;
+    state.next_color += 1;
+    color
+}
+
+fn place_lane(lanes: &mut Vec<Option<GraphLane>>, lane: GraphLane) -> usize {
+    match lanes.iter().position(|l| l.is_none()) {
+        Some(index) => {
+            lanes[index] = Some(lane);
+            index
+        }
+        None => {
+            lanes.push(Some(lane));
+            lanes.len() - 1
+        }
+    }
+}
+
+fn lane_expecting(lanes: &[Option<GraphLane>], hash: &str, skip: usize) -> Option<usize> {
+    lanes
+        .iter()
+        .enumerate()
+        .position(|(index, l)| index != skip && l.as_ref().is_some_and(|l| l.hash == hash))
+}
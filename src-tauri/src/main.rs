@@ -6,6 +6,7 @@ mod git_hub;
 mod azure;
 mod models;
 mod utils;
+mod graph;
 mod tests;
 mod authentication;
 
@@ -44,6 +45,9 @@ fn main() {
             commands::branch::get_branch_status,
             commands::branch::get_current_branch,
             commands::branch::checkout_branch,
+            commands::branch::preview_checkout,
+            commands::branch::checkout_target,
+            commands::branch::create_branch_from_head,
             commands::branch::create_branch,
             commands::branch::checkout_remote_branch,
             commands::branch::delete_branch,
@@ -54,12 +58,29 @@ fn main() {
             commands::branch::get_file_metadata,
             commands::commit::list_commits,
             commands::commit::list_user_commits,
+            commands::commit::list_commits_page,
             commands::commit::get_commit_details,
             commands::commit::git_commit,
+            commands::commit::list_recent_authors,
+            commands::hooks::list_hooks,
+            commands::tag::create_tag,
+            commands::commit_policy::get_commit_policy,
+            commands::commit_policy::save_commit_policy,
+            commands::commit_policy::get_commit_template,
+            commands::commit_policy::validate_commit_message,
             commands::commit::get_commit_file_diff,
+            commands::commit::get_ref_diff,
             commands::commit::get_last_commit_for_path,
             commands::commit::get_path_history,
             commands::commit::list_directory_with_commits,
+            commands::blame::get_blame,
+            commands::blame::get_blame_prior,
+            commands::archive::export_archive,
+            commands::search::search_code,
+            commands::search::cancel_code_search,
+            commands::commit_index::rebuild_commit_index,
+            commands::commit_index::update_commit_index,
+            commands::commit_index::search_commit_index,
             commands::stage::list_local_changes,
             commands::stage::stage_files,
             commands::stage::unstage_files,
@@ -68,6 +89,8 @@ fn main() {
             commands::stage::stash_changes,
             commands::stage::stash_pop,
             commands::stage::reset_hard,
+            commands::stage::preview_reset,
+            commands::stage::reset_to,
             commands::repo::push_repo,
             commands::repo::git_pull,
             commands::repo::git_config_pull,
@@ -84,6 +107,40 @@ fn main() {
             commands::vs_code::open_vscode_git_diff,
             commands::image::load_image_base64,
             commands::merge::merge_branch,
+            commands::sequencer::cherry_pick,
+            commands::sequencer::cherry_pick_onto,
+            commands::sequencer::cherry_pick_continue,
+            commands::sequencer::cherry_pick_abort,
+            commands::sequencer::cherry_pick_onto_continue,
+            commands::sequencer::cherry_pick_onto_abort,
+            commands::sequencer::revert,
+            commands::sequencer::revert_continue,
+            commands::sequencer::revert_abort,
+            commands::rebase::get_rebase_plan,
+            commands::rebase::rebase_interactive,
+            commands::rebase::rebase_continue,
+            commands::rebase::rebase_skip,
+            commands::rebase::rebase_abort,
+            commands::rebase::get_rebase_status,
+            commands::reflog::get_reflog,
+            commands::reflog::restore_reflog_entry,
+            commands::reflog::undo_last_operation,
+            commands::bisect::bisect_start,
+            commands::bisect::bisect_mark,
+            commands::bisect::get_bisect_status,
+            commands::bisect::bisect_reset,
+            commands::bisect::bisect_run,
+            commands::patch::export_patches,
+            commands::patch::check_patch,
+            commands::patch::apply_patch,
+            commands::patch::apply_mailbox,
+            commands::patch::mailbox_continue,
+            commands::patch::mailbox_skip,
+            commands::patch::mailbox_abort,
+            commands::author::rewrite_commit_author,
+            commands::hunk::stage_hunks,
+            commands::hunk::unstage_hunks,
+            commands::hunk::discard_hunks,
             commands::merge::save_file,
             commands::pull_request::open_pull_request,
             commands::git_config::get_git_config,
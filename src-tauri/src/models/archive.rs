@@ -0,0 +1,12 @@
+use serde::Serialize;
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveProgress {
+    pub status: String, // "running", "finished" ou "error"
+    pub files_done: usize,
+    pub total_files: usize,
+    pub current_file: Option<String>,
+    pub output_path: String,
+    pub error: Option<String>,
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitAuthor {
+    pub name: String,
+    pub email: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorRewriteOptions {
+    pub base: Option<String>,      // None = todos os commits que não estão em nenhum remoto
+    pub old_email: Option<String>, // Só commits com este e-mail; None = todos do intervalo
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorRewriteResult {
+    pub branch: String,
+    pub old_head: String,
+    pub new_head: String,
+    pub total: usize,     // Commits do intervalo reescritos (os filhos mudam de hash também)
+    pub rewritten: usize, // Commits que tiveram o autor alterado
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum BisectVerdict {
+    Good,
+    Bad,
+    Skip,
+}
+
+impl BisectVerdict {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BisectVerdict::Good => "good",
+            BisectVerdict::Bad => "bad",
+            BisectVerdict::Skip => "skip",
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BisectCandidate {
+    pub hash: String,
+    pub subject: String,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BisectStatus {
+    pub in_progress: bool,
+    pub current: Option<String>,          // Commit em teste (HEAD)
+    pub bad: Option<String>,
+    pub good: Vec<String>,
+    pub skipped: Vec<String>,
+    pub remaining: usize,                 // Commits que ainda podem ser o primeiro ruim
+    pub estimated_steps: u32,
+    pub first_bad: Option<String>,
+    pub candidates: Vec<BisectCandidate>, // Início da faixa restante, do mais novo para o mais antigo
+    pub message: String,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BisectOutputEvent {
+    pub stream: String, // "stdout" ou "stderr"
+    pub line: String,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BisectRunFinished {
+    pub command: String,
+    pub status: Option<BisectStatus>,
+    pub error: Option<String>,
+}
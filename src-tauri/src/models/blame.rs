@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BlameOptions {
+    pub ignore_whitespace: bool,
+    pub detect_moves: bool,
+    pub detect_copies: bool,
+    /// `None` mantém o `blame.ignoreRevsFile` configurado; `Some(false)` desliga a lista
+    pub ignore_revs: Option<bool>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlameLine {
+    pub line_number: usize,
+    pub original_line_number: usize,
+    pub content: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlameHunk {
+    pub hash: String,
+    pub author: String,
+    pub email: String,
+    pub date: i64,          // Timestamp Unix (author-time)
+    pub timezone: String,   // Ex: "-0300"
+    pub summary: String,
+    pub file_path: String,  // Caminho do arquivo no commit de origem
+    pub previous_hash: Option<String>,
+    pub previous_path: Option<String>,
+    pub boundary: bool,
+    pub lines: Vec<BlameLine>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlameResult {
+    pub revision: String,
+    pub file_path: String,
+    pub hunks: Vec<BlameHunk>,
+    pub focus_line: Option<usize>,
+}
@@ -4,9 +4,14 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "camelCase")]
 pub struct FileContentResponse {
     pub is_image: bool,
+    pub is_binary: bool,
     pub content: String,
     pub size: usize,
     pub line_count: Option<usize>,
+    pub mime_type: Option<String>,
+    pub encoding: Option<String>,
+    pub truncated: bool,
+    pub next_offset: Option<usize>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -14,4 +19,26 @@ pub struct FileContentResponse {
 pub struct FileMetadataResponse {
     pub is_binary: bool,
     pub size: usize,
-}
\ No newline at end of file
+    pub encoding: Option<String>,
+}
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckoutPreview {
+    pub target: String,
+    pub is_local_branch: bool,
+    pub dirty_files: Vec<String>,
+    pub conflicting_files: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckoutResult {
+    pub success: bool,
+    pub head: String,       // Nome da branch ou hash curto quando em detached HEAD
+    pub detached: bool,
+    pub stashed: bool,
+    pub stash_kept: bool,   // Stash não foi removido porque a reaplicação teve conflitos
+    pub conflicting_files: Vec<String>,
+    pub reapply_conflicts: Vec<String>,
+    pub message: String,
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitTrailer {
+    pub key: String,
+    pub value: String,
+}
+
+impl CommitTrailer {
+    /// Lê a saída de `%(trailers:only,unfold)`: uma linha "Chave: valor" por trailer
+    pub fn parse_block(raw: &str) -> Vec<Self> {
+        raw.lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| CommitTrailer { key: key.trim().to_string(), value: value.trim().to_string() })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedCommit {
+    pub hash: String,
+    pub author: String,
+    pub email: String,
+    pub date: i64, // Timestamp Unix do autor
+    pub subject: String,
+    pub body: String,
+    pub trailers: Vec<CommitTrailer>,
+    pub paths: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitIndexStatus {
+    pub indexed_commits: usize,
+    pub added_commits: usize,
+}
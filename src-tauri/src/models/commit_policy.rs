@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ConventionalCommitsRule {
+    pub enabled: bool,
+    pub types: Vec<String>,  // Vazio = tipos padrão (feat, fix, docs...)
+    pub scopes: Vec<String>, // Vazio = qualquer escopo
+    pub require_scope: bool,
+}
+
+/// Regras compartilhadas pelo time em `.devbrook/commit-policy.json`
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CommitPolicy {
+    pub conventional_commits: ConventionalCommitsRule,
+    pub subject_max_length: Option<usize>,
+    pub imperative_mood: bool,
+    pub ticket_pattern: Option<String>, // Regex, ex: "[A-Z]+-\\d+"
+    pub forbidden_words: Vec<String>,
+    pub body_max_line_length: Option<usize>,
+    pub use_commit_template: bool,      // Recusa a mensagem se o commit.template não foi editado
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ViolationSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyViolation {
+    pub rule: String,
+    pub severity: ViolationSeverity,
+    pub message: String,
+    pub line: usize,          // Linha da mensagem completa, começando em 1
+    pub start: Option<usize>, // Coluna (em caracteres) para destacar na UI
+    pub end: Option<usize>,
+}
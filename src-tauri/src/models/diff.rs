@@ -0,0 +1,99 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum WhitespaceMode {
+    IgnoreAll,    // -w
+    IgnoreChange, // -b
+    IgnoreEol,    // --ignore-space-at-eol
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum WordDiffMode {
+    Word,
+    Char,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffAlgorithm {
+    Myers,
+    Minimal,
+    Patience,
+    Histogram,
+}
+
+/// Opções compartilhadas pelos comandos de diff (stage, working tree, commit e entre refs).
+/// Campos vazios mantêm o comportamento padrão do git.
+#[derive(Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DiffOptions {
+    pub whitespace: Option<WhitespaceMode>,
+    pub ignore_blank_lines: bool,
+    pub context_lines: Option<u32>,
+    pub no_renames: bool,
+    pub rename_threshold: Option<u32>,   // Porcentagem de similaridade (-M<n>%)
+    pub copy_threshold: Option<u32>,     // Ativa detecção de cópias (-C<n>%)
+    pub word_diff: Option<WordDiffMode>, // Saída em --word-diff=porcelain
+    pub algorithm: Option<DiffAlgorithm>,
+    pub textconv: Option<bool>,          // Filtros textconv do .gitattributes
+}
+
+impl DiffOptions {
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        match self.whitespace {
+            Some(WhitespaceMode::IgnoreAll) => args.push("-w".into()),
+            Some(WhitespaceMode::IgnoreChange) => args.push("-b".into()),
+            Some(WhitespaceMode::IgnoreEol) => args.push("--ignore-space-at-eol".into()),
+            None => {}
+        }
+        if self.ignore_blank_lines {
+            args.push("--ignore-blank-lines".into());
+        }
+        if let Some(lines) = self.context_lines {
+            args.push(format!("-U{}", lines));
+        }
+
+        if self.no_renames {
+            args.push("--no-renames".into());
+        } else {
+            match self.rename_threshold {
+                Some(threshold) => args.push(format!("-M{}%", threshold.min(100))),
+                None => args.push("-M".into()),
+            }
+            if let Some(threshold) = self.copy_threshold {
+                args.push(format!("-C{}%", threshold.min(100)));
+            }
+        }
+
+        match self.word_diff {
+            Some(WordDiffMode::Word) => args.push("--word-diff=porcelain".into()),
+            Some(WordDiffMode::Char) => {
+                args.push("--word-diff=porcelain".into());
+                args.push("--word-diff-regex=.".into());
+            }
+            None => {}
+        }
+
+        if let Some(algorithm) = self.algorithm {
+            let name = match algorithm {
+                DiffAlgorithm::Myers => "myers",
+                DiffAlgorithm::Minimal => "minimal",
+                DiffAlgorithm::Patience => "patience",
+                DiffAlgorithm::Histogram => "histogram",
+            };
+            args.push(format!("--diff-algorithm={}", name));
+        }
+
+        match self.textconv {
+            Some(true) => args.push("--textconv".into()),
+            Some(false) => args.push("--no-textconv".into()),
+            None => {}
+        }
+
+        args
+    }
+}
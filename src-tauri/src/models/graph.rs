@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphLane {
+    pub hash: String, // Próximo commit esperado nesta coluna
+    pub color: usize,
+}
+
+/// Estado das colunas ao final de uma página; o frontend devolve na próxima requisição.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphState {
+    pub lanes: Vec<Option<GraphLane>>,
+    pub next_color: usize,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum EdgeKind {
+    Pass, // Linha que atravessa a linha inteira sem tocar no commit
+    In,   // Metade de cima: chega no commit
+    Out,  // Metade de baixo: sai do commit para um pai
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphEdge {
+    pub from: usize,
+    pub to: usize,
+    pub color: usize,
+    pub kind: EdgeKind,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphRow {
+    pub column: usize,
+    pub color: usize,
+    pub width: usize,
+    pub edges: Vec<GraphEdge>,
+}
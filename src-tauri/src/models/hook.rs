@@ -0,0 +1,32 @@
+use serde::Serialize;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookInfo {
+    pub name: String,
+    pub path: String,
+    pub executable: bool,
+    pub from_hooks_path: bool, // true quando vem de core.hooksPath (husky, lefthook...)
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookOutputEvent {
+    pub operation: String, // "commit" ou "push"
+    pub stream: String,    // "stdout" ou "stderr"
+    pub line: String,
+}
+
+/// Erro de commit/push que diferencia a falha de um hook das demais falhas do Git
+#[derive(Serialize, Debug)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum GitCommandError {
+    Hook { hook: String, message: String },
+    Git { message: String },
+}
+
+impl From<String> for GitCommandError {
+    fn from(message: String) -> Self {
+        GitCommandError::Git { message }
+    }
+}
@@ -0,0 +1,32 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffSide {
+    Old, // Linhas removidas ("-"), numeradas pelo arquivo antigo
+    New, // Linhas adicionadas ("+"), numeradas pelo arquivo novo
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct LineRange {
+    pub side: DiffSide,
+    pub start: u32,
+    pub end: u32, // Inclusivo
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HunkSelection {
+    pub header: String,                // "@@ -10,7 +10,8 @@" exatamente como veio no diff
+    pub lines: Option<Vec<LineRange>>, // None = hunk inteiro
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HunkPatchRequest {
+    pub file: String,
+    pub index: String, // "<antigo>..<novo>" da linha `index` do diff exibido
+    pub hunks: Vec<HunkSelection>,
+    pub context_lines: Option<u32>, // Mesmo -U usado para exibir o diff, senão os cabeçalhos não batem
+}
@@ -2,4 +2,21 @@ pub mod pull;
 pub mod license;
 pub mod dashboard;
 pub mod branch;
-pub mod test;
\ No newline at end of file
+pub mod test;
+pub mod blame;
+pub mod archive;
+pub mod search;
+pub mod graph;
+pub mod commit_index;
+pub mod signature;
+pub mod commit_policy;
+pub mod hook;
+pub mod sequencer;
+pub mod rebase;
+pub mod reset;
+pub mod reflog;
+pub mod diff;
+pub mod bisect;
+pub mod patch;
+pub mod author;
+pub mod hunk;
\ No newline at end of file
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PatchExportOptions {
+    pub mbox: bool,                     // Um único arquivo com toda a série
+    pub cover_letter: bool,
+    pub numbered: Option<bool>,         // true = [PATCH n/m], false = sem numeração, None = padrão do git
+    pub start_number: Option<u32>,
+    pub subject_prefix: Option<String>, // Padrão "PATCH"
+    pub reroll_count: Option<u32>,      // -v<n>, ex: [PATCH v2]
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchExportResult {
+    pub files: Vec<String>,
+    pub commits: usize,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum PatchApplyTarget {
+    Worktree,
+    Index, // Só o índice (--cached)
+    Both,  // Índice e working tree (--index)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchCheckResult {
+    pub applies: bool,
+    pub files: Vec<String>,        // Arquivos tocados pelo patch
+    pub failed_files: Vec<String>, // Arquivos em que algum trecho não aplica
+    pub message: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchApplyResult {
+    pub success: bool,
+    pub conflicting_files: Vec<String>, // Conflitos deixados pelo --3way
+    pub message: String,
+}
+
+/// Estado de um `git am`: quantos patches da série foram aplicados e onde parou.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MailboxStatus {
+    pub in_progress: bool,
+    pub current: usize,
+    pub total: usize,
+    pub failed_subject: Option<String>,
+    pub conflicting_files: Vec<String>,
+    pub message: String,
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RebaseAction {
+    Pick,
+    Reword,
+    Edit,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RebaseAction::Pick => "pick",
+            RebaseAction::Reword => "reword",
+            RebaseAction::Edit => "edit",
+            RebaseAction::Squash => "squash",
+            RebaseAction::Fixup => "fixup",
+            RebaseAction::Drop => "drop",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pick" | "p" => Some(RebaseAction::Pick),
+            "reword" | "r" => Some(RebaseAction::Reword),
+            "edit" | "e" => Some(RebaseAction::Edit),
+            "squash" | "s" => Some(RebaseAction::Squash),
+            "fixup" | "f" => Some(RebaseAction::Fixup),
+            "drop" | "d" => Some(RebaseAction::Drop),
+            _ => None,
+        }
+    }
+}
+
+/// Um passo do plano enviado pela interface, na ordem em que deve ser aplicado.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebaseStep {
+    pub action: RebaseAction,
+    pub sha: String,
+    pub message: Option<String>, // Nova mensagem para reword/squash
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RebaseOptions {
+    pub autosquash: bool, // Reordena commits "fixup!"/"squash!" quando o plano vem vazio
+    pub autostash: bool,
+}
+
+/// Linha do todo sugerido pelo git (já com autosquash aplicado, se pedido).
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebaseTodoItem {
+    pub action: RebaseAction,
+    pub sha: String,
+    pub subject: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RebaseStopReason {
+    Conflict,
+    Edit,
+    Other, // Ex: exec falhou ou conflito resolvido mas ainda não continuado
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebaseStatus {
+    pub in_progress: bool,
+    pub done: usize,
+    pub total: usize,
+    pub stopped_at: Option<String>,
+    pub stop_reason: Option<RebaseStopReason>,
+    pub conflicting_files: Vec<String>,
+    pub message: String,
+}
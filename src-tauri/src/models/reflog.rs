@@ -0,0 +1,23 @@
+use serde::Serialize;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReflogEntry {
+    pub selector: String,  // Ex: "HEAD@{2}"
+    pub hash: String,
+    pub action: String,    // Ex: "commit (amend)", "reset", "rebase (finish)"
+    pub message: String,
+    pub timestamp: i64,    // Momento da mudança da ref (não do commit)
+}
+
+/// Como o último passo do reflog foi (ou seria) desfeito.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoResult {
+    pub action: String,
+    pub reference: String, // Branch ou HEAD restaurado
+    pub from: String,
+    pub to: String,
+    pub applied: bool,     // false quando for apenas uma prévia
+    pub message: String,
+}
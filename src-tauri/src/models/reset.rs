@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ResetMode {
+    Soft,  // Mantém índice e arquivos; os commits viram alterações no stage
+    Mixed, // Mantém os arquivos, limpa o stage
+    Hard,  // Descarta tudo que não foi commitado
+    Keep,  // Como hard, mas recusa se alguma alteração local fosse perdida
+}
+
+impl ResetMode {
+    pub fn flag(&self) -> &'static str {
+        match self {
+            ResetMode::Soft => "--soft",
+            ResetMode::Mixed => "--mixed",
+            ResetMode::Hard => "--hard",
+            ResetMode::Keep => "--keep",
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetCommit {
+    pub hash: String,
+    pub subject: String,
+    pub author: String,
+    pub date: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetPreview {
+    pub target: String,                     // Hash completo do destino
+    pub mode: ResetMode,
+    pub leaving_commits: Vec<ResetCommit>,  // Commits que deixam de fazer parte da branch
+    pub lost_files: Vec<String>,            // Alterações locais que seriam descartadas (hard)
+    pub untracked_overwritten: Vec<String>, // Arquivos não versionados sobrescritos pelo destino (hard)
+    pub blocking_files: Vec<String>,        // Arquivos que fazem o modo keep recusar o reset
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CodeSearchOptions {
+    pub regex: bool,
+    pub case_sensitive: bool,
+    pub whole_word: bool,
+    pub include: Vec<String>, // Globs, ex: "src/**/*.rs"
+    pub exclude: Vec<String>,
+    pub max_results: Option<usize>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeSearchMatch {
+    pub file_path: String,
+    pub line_number: usize,
+    pub column: usize,
+    pub snippet: String,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeSearchBatch {
+    pub search_id: String,
+    pub matches: Vec<CodeSearchMatch>,
+    pub done: bool,
+    pub cancelled: bool,
+    pub truncated: bool,
+    pub error: Option<String>,
+}
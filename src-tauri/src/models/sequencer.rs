@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CherryPickOptions {
+    pub record_origin: bool,   // -x
+    pub mainline: Option<u32>, // -m <pai> para commits de merge
+    pub no_commit: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RevertOptions {
+    pub mainline: Option<u32>,
+    pub single_commit: bool,     // Grava todas as reversões em um único commit
+    pub message: Option<String>, // Substitui a mensagem gerada no modo de commit único
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SequencerConflict {
+    pub commit: String,
+    pub files: Vec<String>, // Vazio quando a parada não foi por conflito (ex: commit ficou vazio)
+}
+
+/// Resultado de cherry-pick/revert: o que foi aplicado e onde parou.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SequencerResult {
+    pub success: bool,
+    pub completed: Vec<String>,
+    pub remaining: Vec<String>,
+    pub conflict: Option<SequencerConflict>,
+    pub in_progress: bool,
+    pub message: String,
+    pub worktree: Option<String>, // Worktree mantido para resolver conflitos de um cherry-pick em outra branch
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SigningOptions {
+    pub sign: bool,
+    pub format: Option<String>, // "openpgp" (GPG), "ssh" ou "x509"
+    pub key: Option<String>,    // Key id do GPG ou caminho da chave SSH
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SignatureStatus {
+    Good,
+    Bad,
+    UnknownKey,
+    Expired,
+    None,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureInfo {
+    pub status: SignatureStatus,
+    pub signer: Option<String>,
+    pub fingerprint: Option<String>,
+}
+
+impl SignatureInfo {
+    /// Converte os placeholders `%G?`, `%GS` e `%GF` do git log
+    pub fn from_git(code: &str, signer: &str, fingerprint: &str) -> Self {
+        let status = match code.trim() {
+            // U: assinatura válida, mas a confiança na chave não foi estabelecida
+            "G" | "U" => SignatureStatus::Good,
+            "B" | "R" => SignatureStatus::Bad,
+            "X" | "Y" => SignatureStatus::Expired,
+            "E" => SignatureStatus::UnknownKey,
+            _ => SignatureStatus::None,
+        };
+
+        let non_empty = |s: &str| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+
+        SignatureInfo {
+            status,
+            signer: non_empty(signer),
+            fingerprint: non_empty(fingerprint),
+        }
+    }
+}
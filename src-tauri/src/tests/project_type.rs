@@ -9,6 +9,20 @@ pub struct ProjectType {
     pub test_runner: String, // "Karma", "Jest", "Vitest", "GoTest", etc.
 }
 
+impl ProjectType {
+    /// Comando que roda a suíte inteira sem interação (usado pelo bisect automático)
+    pub fn test_command(&self) -> Option<&'static str> {
+        match self.test_runner.as_str() {
+            "Karma/Jasmine" => Some("npx ng test --watch=false --progress=false"),
+            "Vitest" => Some("npx vitest run"),
+            "Jest" => Some("npx jest"),
+            "GoTest" => Some("go test ./..."),
+            "dotnet test" => Some("dotnet test"),
+            _ => None,
+        }
+    }
+}
+
 #[tauri::command]
 pub async fn detect_project_type(project_path: String) -> Result<ProjectType, String> {
     let path = Path::new(&project_path);
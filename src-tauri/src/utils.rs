@@ -30,4 +30,33 @@ pub fn git_command_async(path: &str) -> TokioCommand {
     }
 
     cmd
-}
\ No newline at end of file
+}
+
+/// Executa o git e devolve o stdout, ou o stderr como erro
+pub fn run_git(repo_path: &str, args: &[&str]) -> Result<String, String> {
+    let output = git_command(repo_path)
+        .args(args)
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).to_string())
+    }
+}
+
+/// Versão assíncrona de `run_git`
+pub async fn run_git_async(repo_path: &str, args: &[&str]) -> Result<String, String> {
+    let output = git_command_async(repo_path)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).to_string())
+    }
+}
@@ -41,7 +41,7 @@ export function CommitDetails(props: CommitDetailsProps) {
     setSelectedFile(file);
     setLoadingDiff(true);
     try {
-      const res = await getCommitFileDiff(props.repoPath, props.commit.hash, file.file);
+      const res = await getCommitFileDiff(props.repoPath, props.commit.hash, file.file, file.oldPath ?? undefined, undefined, props.commit.diffParent ?? undefined);
       console.log("Diff recebido:", res);
       setFileDiff(res);
     } catch (e) {
@@ -6,12 +6,18 @@ export type Branch = {
 
 export type BranchFileContentResponse = {
     isImage: boolean;
+    isBinary: boolean;
     content: string;
     size: number;
     lineCount: number;
+    mimeType?: string;
+    encoding?: string;
+    truncated: boolean;
+    nextOffset?: number;
 }
 
 export type BranchFileMetadataResponse = {
     isBinary: boolean;
     size: number;
+    encoding?: string;
 }
\ No newline at end of file
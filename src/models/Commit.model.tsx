@@ -4,6 +4,12 @@ export interface Commit {
   author: string;
   email: string;
   date: string;
+  authorTimestamp: number;
+  authorTzOffset: number; // Minutos em relação ao UTC
+  committerName: string;
+  committerEmail: string;
+  committerTimestamp: number;
+  committerTzOffset: number;
 }
 
 export interface CommitDetail extends Commit {
@@ -13,13 +19,19 @@ export interface CommitDetail extends Commit {
   subject: string;
   body: string;
   parents: string[];
+  diffParent: string | null;
+  combined: boolean;
   files: CommitFile[];
 }
 
 export interface CommitFile {
   file: string;
-  status: string; // M, A, D, R...
-  changes: string;
+  status: string; // M, A, D, R... (no diff combinado, uma letra por pai)
+  oldPath: string | null;
+  similarity: number | null;
+  additions: number | null;
+  deletions: number | null;
+  binary: boolean;
 }
 
 export interface FileEntry {
@@ -2,4 +2,16 @@ export type Diff = {
     diff: string;
     oldFile?: string;
     newFile?: string;
-}
\ No newline at end of file
+}
+
+export type DiffOptions = {
+    whitespace?: "ignoreAll" | "ignoreChange" | "ignoreEol";
+    ignoreBlankLines?: boolean;
+    contextLines?: number;
+    noRenames?: boolean;
+    renameThreshold?: number;
+    copyThreshold?: number;
+    wordDiff?: "word" | "char";
+    algorithm?: "myers" | "minimal" | "patience" | "histogram";
+    textconv?: boolean;
+}
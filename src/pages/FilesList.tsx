@@ -2,7 +2,7 @@ import { createSignal, createEffect, Show, For, onMount, onCleanup, createMemo,
 
 import { Repo } from "../models/Repo.model";
 import { FolderTreeView } from "../components/ui/FolderTreeview";
-import { listBranchFiles, getBranchFileContent, getLastCommitForPath, listDirectory, getPathHistory, getCommitDetails, getBranchFileMetadata } from "../services/gitService";
+import { listBranchFiles, getBranchFileContent, getLastCommitForPath, listDirectory, onDirectoryCommits, getPathHistory, getCommitDetails, getBranchFileMetadata } from "../services/gitService";
 import { useLoading } from "../components/ui/LoadingContext";
 import { Commit, FileEntry } from "../models/Commit.model";
 import { getGravatarUrl } from "../services/gravatarService";
@@ -36,6 +36,8 @@ export default function FileList(props: { repo: Repo }) {
   const [showHistory, setShowHistory] = createSignal(false);
   const [searchTerm, setSearchTerm] = createSignal("");
   const [isBinary, setIsBinary] = createSignal(false);
+  const [nextOffset, setNextOffset] = createSignal<number | null>(null);
+  const [loadingMore, setLoadingMore] = createSignal(false);
   const { t, locale } = useApp();
 
   const UNSUPPORTED_EXTENSIONS = [
@@ -46,6 +48,21 @@ export default function FileList(props: { repo: Repo }) {
 
   const { showLoading, hideLoading } = useLoading();
 
+  // O último commit de cada entrada chega depois que o diretório já foi exibido
+  onMount(async () => {
+    const unlisten = await onDirectoryCommits((batch) => {
+      if (batch.repoPath !== props.repo.path || batch.branch !== selectedBranch()) return;
+
+      const commits = new Map(batch.entries.map(e => [batch.folderPath ? `${batch.folderPath}/${e.name}` : e.name, e.lastCommit]));
+      setDirectoryContent(prev => prev?.map(entry => {
+        const lastCommit = commits.get(entry.path);
+        return lastCommit ? { ...entry, lastCommit } : entry;
+      }) ?? prev);
+    });
+
+    onCleanup(() => unlisten());
+  });
+
   let lastRepoPath = props.repo.path;
   createEffect(() => {
     const currentPath = props.repo.path;
@@ -98,6 +115,7 @@ export default function FileList(props: { repo: Repo }) {
       const unsupported = UNSUPPORTED_EXTENSIONS.includes(extension);
       
       setIsBinary(unsupported);
+      setNextOffset(null);
 
       if (unsupported) {
         setFileContent(""); // Limpa conteúdo anterior
@@ -116,7 +134,9 @@ export default function FileList(props: { repo: Repo }) {
         const data = await getBranchFileContent(props.repo.path, selectedBranch(), path);
         
         setIsImage(data.isImage);
+        setIsBinary(data.isBinary);
         setFileContent(data.content);
+        setNextOffset(data.truncated ? data.nextOffset ?? null : null);
         setFileMeta({size: data.size, lines: data.lineCount})
         setSelectedFilePath([path]);
         setDirectoryContent(null);
@@ -137,6 +157,25 @@ export default function FileList(props: { repo: Repo }) {
     getLastCommit(path);
   };
 
+  // Arquivos grandes chegam em páginas; cada página é anexada ao conteúdo já exibido
+  const loadMoreContent = async () => {
+    const offset = nextOffset();
+    const path = selectedFilePath()[0];
+    if (offset === null || !path || loadingMore()) return;
+
+    setLoadingMore(true);
+    try {
+      const data = await getBranchFileContent(props.repo.path, selectedBranch(), path, offset);
+      if (selectedFilePath()[0] !== path) return;
+      setFileContent(prev => (prev ?? "") + data.content);
+      setNextOffset(data.truncated ? data.nextOffset ?? null : null);
+    } catch (e) {
+      console.error("Erro ao carregar o restante do arquivo:", e);
+    } finally {
+      setLoadingMore(false);
+    }
+  };
+
   const allBranchOptions = createMemo(() => {
     const options: SearchableSelectOption[] = [];
 
@@ -428,6 +467,21 @@ export default function FileList(props: { repo: Repo }) {
                   {/* Caso 3: Texto */}
                   <Match when={!isImage() && !isBinary()}>
                     <CodePreviewer fileName={getSelectedFileName()} content={fileContent() || ''} />
+                    <Show when={nextOffset() !== null}>
+                      <div class="flex items-center justify-between gap-2 p-2 text-sm bg-yellow-100 dark:bg-yellow-900/40 rounded-b-xl">
+                        <span>
+                          <i class="fa-solid fa-scissors mr-2" />
+                          {t('file').big_file}: {t('file').showing} {formatSize(nextOffset()!)} {t('file').of} {formatSize(fileMeta()?.size || 0)}.
+                        </span>
+                        <button
+                          class="px-3 py-1 rounded bg-gray-200 dark:bg-gray-700 hover:bg-gray-300 dark:hover:bg-gray-600 disabled:opacity-50"
+                          disabled={loadingMore()}
+                          onClick={loadMoreContent}
+                        >
+                          {loadingMore() ? t('file').loading_more : t('file').load_more}
+                        </button>
+                      </div>
+                    </Show>
                   </Match>
                 </Switch>
 
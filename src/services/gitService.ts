@@ -1,8 +1,9 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { Branch, BranchFileContentResponse, BranchFileMetadataResponse } from "../models/Banch.model";
-import { Diff } from "../models/Diff.model";
+import { Diff, DiffOptions } from "../models/Diff.model";
 import { GitPullResult } from "../models/Pull.model";
-import { Commit, FileEntry } from "../models/Commit.model";
+import { Commit, CommitFile, FileEntry } from "../models/Commit.model";
 import { CoverageStats } from "../models/Dashboard.model";
 
 export async function validateRepo(path: string): Promise<string> {
@@ -67,15 +68,30 @@ export async function listDirectory(repoPath: string, branch: string, folderPath
   );
 }
 
-export async function getCommitDetails(path: string, hash: string) {
+export type DirectoryCommitsBatch = {
+  repoPath: string;
+  branch: string;
+  folderPath: string;
+  entries: { name: string; lastCommit: Commit }[];
+  done: boolean;
+};
+
+// Último commit das entradas de listDirectory, enviado em lotes quando não estava em cache
+export async function onDirectoryCommits(callback: (batch: DirectoryCommitsBatch) => void) {
+  return await listen<DirectoryCommitsBatch>("directory-commits", (event) => callback(event.payload));
+}
+
+export async function getCommitDetails(path: string, hash: string, parent?: number, combined?: boolean) {
   return await invoke<{
     hash: string;
     authorName: string;
     authorEmail: string;
     authorDate: string;
     subject: string;
-    files: { file: string; changes: string }[];
-  }>("get_commit_details", { path, hash });
+    diffParent: string | null;
+    combined: boolean;
+    files: CommitFile[];
+  }>("get_commit_details", { path, hash, parent, combined });
 }
 
 export async function getLocalChanges(path: string) {
@@ -99,7 +115,8 @@ export async function discard_changes(repoPath: string, paths: string[]) {
 export async function getDiff(
   repoPath: string,
   file: string,
-  staged: boolean = false
+  staged: boolean = false,
+  options?: DiffOptions
 ): Promise<Diff> {
   let relativeFile =file;
   if (file.startsWith(repoPath)) {
@@ -109,32 +126,66 @@ export async function getDiff(
     repoPath,
     file: relativeFile,
     staged,
+    options,
   });
 }
 
+export type GitCommandError =
+  | { kind: "hook"; hook: string; message: string }
+  | { kind: "git"; message: string };
+
+// Mantém `${err}` legível nas telas que já tratam o erro como texto
+function toGitError(err: unknown): Error & Partial<GitCommandError> {
+  if (typeof err === "string") return new Error(err);
+  const gitErr = err as GitCommandError;
+  const prefix = gitErr.kind === "hook" ? `Hook ${gitErr.hook} falhou:\n` : "";
+  return Object.assign(new Error(prefix + gitErr.message), gitErr);
+}
+
 export async function commit(
   repoPath: string,
   message: string,
   description: string,
-  amend: boolean
+  amend: boolean,
+  options?: {
+    signing?: { sign: boolean; format?: string; key?: string };
+    trailers?: { key: string; value: string }[];
+    signOff?: boolean;
+    noVerify?: boolean;
+    resetAuthor?: boolean;
+    author?: { name: string; email: string };
+    date?: string;
+    noEdit?: boolean;
+  }
 ): Promise<string> {
-  return await invoke("git_commit", {
-    repoPath,
-    message,
-    description,
-    amend,
-  });
+  try {
+    return await invoke("git_commit", {
+      repoPath,
+      message,
+      description,
+      amend,
+      options,
+    });
+  } catch (err) {
+    throw toGitError(err);
+  }
 }
 
 export async function getCommitFileDiff(
   repoPath: string,
   commitSha: string,
-  filePath: string
+  filePath: string,
+  oldPath?: string,
+  options?: DiffOptions,
+  parent?: string
 ): Promise<any> {
   return await invoke("get_commit_file_diff", {
     repoPath,
     commitSha,
-    filePath
+    filePath,
+    oldPath,
+    parent,
+    options
   });
 }
 
@@ -143,9 +194,14 @@ export async function pushRepo(
   remote: string = "origin",
   branch: string = "HEAD",
   token?: string,
-  provider?: string
+  provider?: string,
+  noVerify?: boolean
 ): Promise<string> {
-  return await invoke("push_repo", { path: repoPath, remote, branch, token, provider });
+  try {
+    return await invoke("push_repo", { path: repoPath, remote, branch, token, provider, noVerify });
+  } catch (err) {
+    throw toGitError(err);
+  }
 }
 
 export async function pull(repoPath: string, branch: string): Promise<GitPullResult> {
@@ -232,8 +288,8 @@ export async function listBranchFilesWithSize(repoPath: string, branch: string):
   return await invoke("list_branch_files_with_size", { path: repoPath, branch });
 }
 
-export async function getBranchFileContent(repoPath: string, branch: string, filePath: string): Promise<BranchFileContentResponse> {
-  return await invoke("get_branch_file_content", { path: repoPath, branch, filePath });
+export async function getBranchFileContent(repoPath: string, branch: string, filePath: string, offset?: number): Promise<BranchFileContentResponse> {
+  return await invoke("get_branch_file_content", { path: repoPath, branch, filePath, offset });
 }
 
 export async function getBranchFileMetadata(repoPath: string, branch: string, filePath: string): Promise<BranchFileMetadataResponse> {